@@ -0,0 +1,116 @@
+use crate::{Args, Day};
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde_json::{json, Value};
+use std::panic;
+use std::time::Duration;
+
+const STAGES: [&str; 3] = ["parse", "part1", "part2"];
+
+#[derive(Debug, Clone, Copy)]
+struct Stats {
+    min: Duration,
+    median: Duration,
+    max: Duration,
+}
+
+impl Stats {
+    fn new(mut samples: Vec<Duration>) -> Self {
+        samples.sort_unstable();
+        Self {
+            min: samples[0],
+            median: samples[samples.len() / 2],
+            max: samples[samples.len() - 1],
+        }
+    }
+}
+
+fn bench_day(day: &Day, input: &str, runs: usize, variant: Option<&str>) -> Result<[Stats; 3]> {
+    let Some(solver) = day.solver else {
+        bail!("day {} is not implemented yet", day.day);
+    };
+    let mut samples: [Vec<Duration>; 3] = Default::default();
+    for _ in 0..runs {
+        let times = panic::catch_unwind(|| (solver.time)(input, variant))
+            .map_err(|_| eyre!("day {} panicked", day.day))??;
+        for (stage, time) in samples.iter_mut().zip(times) {
+            stage.push(time);
+        }
+    }
+    Ok(samples.map(Stats::new))
+}
+
+/// One line of the `--json` output.
+fn record(day: u8, stage: &str, runs: usize, s: &Stats) -> Value {
+    json!({
+        "day": day,
+        "stage": stage,
+        "runs": runs,
+        "min_ns": s.min.as_nanos() as u64,
+        "median_ns": s.median.as_nanos() as u64,
+        "max_ns": s.max.as_nanos() as u64,
+    })
+}
+
+/// Times every stage of the given days `--runs` times and prints a table,
+/// optionally writing the same numbers to `--json`.
+pub fn run(days: &[&Day], path: Option<&str>, args: &Args) -> Result<()> {
+    let runs = args.runs.unwrap_or(5);
+
+    println!("{:>3}  {:<5}  {:>12}  {:>12}  {:>12}", "day", "stage", "min", "median", "max");
+    let mut records = Vec::new();
+    let mut failed = Vec::new();
+    for day in days {
+        let input = day.load_input(path)?;
+        let stats = match bench_day(day, &input, runs, args.variant.as_deref()) {
+            Ok(stats) => stats,
+            Err(err) => {
+                eprintln!("day {}: failed: {err:#}", day.day);
+                failed.push(day.day);
+                continue;
+            }
+        };
+        for (stage, s) in STAGES.iter().zip(stats) {
+            println!("{:>3}  {:<5}  {:>12.3?}  {:>12.3?}  {:>12.3?}", day.day, stage, s.min, s.median, s.max);
+            records.push(record(day.day, stage, runs, &s));
+        }
+    }
+
+    if let Some(json_path) = &args.json {
+        let text = serde_json::to_string_pretty(&records)?;
+        std::fs::write(json_path, text + "\n").wrap_err_with(|| format!("failed to write {json_path}"))?;
+    }
+    if !failed.is_empty() {
+        bail!("failed days: {failed:?}");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(millis: &[u64]) -> Vec<Duration> {
+        millis.iter().map(|&n| Duration::from_millis(n)).collect()
+    }
+
+    #[test]
+    fn test_stats() {
+        let s = Stats::new(ms(&[7, 1, 3, 9, 5]));
+        assert_eq!(ms(&[1, 5, 9]), [s.min, s.median, s.max]);
+        let s = Stats::new(ms(&[4]));
+        assert_eq!(ms(&[4, 4, 4]), [s.min, s.median, s.max]);
+    }
+
+    #[test]
+    fn test_record() {
+        let s = Stats::new(ms(&[2, 1, 3]));
+        assert_eq!(json!({
+            "day": 14,
+            "stage": "part2",
+            "runs": 3,
+            "min_ns": 1_000_000,
+            "median_ns": 2_000_000,
+            "max_ns": 3_000_000,
+        }), record(14, "part2", 3, &s));
+    }
+}
@@ -0,0 +1,254 @@
+use color_eyre::eyre::{bail, eyre, Result};
+use my_project1::days::*;
+use my_project1::runner::{answer, time_stages, Part, Solution};
+use std::borrow::Cow;
+use std::panic;
+use std::time::Duration;
+
+mod bench;
+mod verify;
+
+type SolveFn = fn(&str, u8, Option<&str>) -> Result<String>;
+type TimeFn = fn(&str, Option<&str>) -> Result<[Duration; 3]>;
+
+#[derive(Debug, Clone, Copy)]
+struct Solver {
+    solve: SolveFn,
+    time: TimeFn,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct Day {
+    day: u8,
+    input: &'static str,
+    solver: Option<Solver>,
+}
+
+impl Day {
+    fn new<S: Solution>(day: u8, input: &'static str) -> Self {
+        Self { day, input, solver: Some(Solver { solve: answer::<S>, time: time_stages::<S> }) }
+    }
+    /// Whether the day can run without `--input`.
+    fn runnable(&self) -> bool {
+        self.solver.is_some() && !self.input.is_empty()
+    }
+    /// Loads the input from `path`, or the bundled input when there is one.
+    fn load_input(&self, path: Option<&str>) -> Result<Cow<'static, str>> {
+        if path.is_none() && self.input.is_empty() {
+            bail!("day {} has no bundled input, pass one with --input", self.day);
+        }
+        my_project1::runner::load_input(path, self.input)
+    }
+}
+
+fn registry() -> Vec<Day> {
+    vec![
+        Day::new::<day1::Day1>(1, day1::INPUT),
+        Day::new::<day2::Day2>(2, day2::INPUT),
+        Day::new::<day3::Day3>(3, day3::INPUT),
+        Day::new::<day4::Day4>(4, day4::INPUT),
+        Day::new::<day5::Day5>(5, day5::INPUT),
+        Day::new::<day6::Day6>(6, day6::INPUT),
+        Day::new::<day7::Day7>(7, day7::INPUT),
+        Day::new::<day8::Day8>(8, day8::INPUT),
+        Day::new::<day9::Day9>(9, day9::INPUT),
+        Day::new::<day10::Day10>(10, day10::INPUT),
+        Day::new::<day11::Day11>(11, day11::INPUT),
+        Day::new::<day12::Day12>(12, day12::INPUT),
+        Day::new::<day13::Day13>(13, day13::INPUT),
+        Day::new::<day14::Day14>(14, day14::INPUT),
+        Day::new::<day15::Day15>(15, day15::INPUT),
+        Day::new::<day16::Day16>(16, day16::INPUT),
+        Day::new::<day17::Day17>(17, day17::INPUT),
+        Day::new::<day18::Day18>(18, day18::INPUT),
+        Day::new::<day19::Day19>(19, day19::INPUT),
+    ]
+}
+
+#[derive(Debug, Default)]
+struct Args {
+    positional: Vec<String>,
+    part: Option<Part>,
+    input: Option<String>,
+    runs: Option<usize>,
+    json: Option<String>,
+    record: bool,
+    variant: Option<String>,
+    answers: Option<String>,
+}
+
+impl Args {
+    fn parse(args: impl Iterator<Item = String>) -> Result<Self> {
+        let mut parsed = Args::default();
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            let mut value = |flag: &str| args.next().ok_or_else(|| eyre!("{flag} needs a value"));
+            match arg.as_str() {
+                "--part" => parsed.part = Some(Part::parse(&value("--part")?)?),
+                "--input" => parsed.input = Some(value("--input")?),
+                "--runs" => parsed.runs = Some(value("--runs")?.parse().ok().filter(|&n| n > 0).ok_or_else(|| eyre!("--runs needs a positive number"))?),
+                "--json" => parsed.json = Some(value("--json")?),
+                "--record" => parsed.record = true,
+                "--variant" => parsed.variant = Some(value("--variant")?),
+                "--answers" => parsed.answers = Some(value("--answers")?),
+                _ if arg.starts_with("--") => bail!("unexpected argument `{arg}`\n{}", usage()),
+                _ => parsed.positional.push(arg),
+            }
+        }
+        Ok(parsed)
+    }
+    /// The first given flag that only makes sense for one day.
+    fn single_day_flag(&self) -> Option<&'static str> {
+        if self.input.is_some() {
+            Some("--input")
+        } else if self.variant.is_some() {
+            Some("--variant")
+        } else {
+            None
+        }
+    }
+}
+
+fn usage() -> &'static str {
+    "usage: aoc <day> [--part 1|2|both] [--input <path>|-] [--variant <name>]
+       aoc all [--part 1|2|both]
+       aoc bench [<day>|all] [--runs <n>] [--json <path>] [--variant <name>]
+       aoc verify [<day>|all] [--part 1|2|both] [--input <path>|-] [--variant <name>] [--answers <path>] [--record]
+       aoc list"
+}
+
+fn list(days: &[Day]) {
+    for day in days {
+        let status = match day.solver {
+            Some(_) if day.input.is_empty() => "implemented, no bundled input",
+            Some(_) => "implemented",
+            None => "unimplemented",
+        };
+        println!("day {:>2}  {}", day.day, status);
+    }
+}
+
+fn find_day<'a>(days: &'a [Day], target: &str) -> Result<&'a Day> {
+    let n: u8 = target.parse().map_err(|_| eyre!("invalid day `{target}`\n{}", usage()))?;
+    days.iter().find(|day| day.day == n).ok_or_else(|| eyre!("no such day: {n}"))
+}
+
+fn run_part(day: u8, n: u8, solve: SolveFn, input: &str, variant: Option<&str>) -> bool {
+    match panic::catch_unwind(|| solve(input, n, variant)) {
+        Ok(Ok(answer)) => {
+            println!("day {day} part {n}: {answer}");
+            true
+        }
+        Ok(Err(err)) => {
+            eprintln!("day {day} part {n}: failed: {err:#}");
+            false
+        }
+        Err(_) => {
+            eprintln!("day {day} part {n}: failed");
+            false
+        }
+    }
+}
+
+fn run_day(day: &Day, part: Part, path: Option<&str>, variant: Option<&str>) -> Result<()> {
+    let Some(solver) = day.solver else {
+        bail!("day {} is not implemented yet", day.day);
+    };
+    let input = day.load_input(path)?;
+    let mut ok = true;
+    if part.includes(1) {
+        ok &= run_part(day.day, 1, solver.solve, &input, variant);
+    }
+    if part.includes(2) {
+        ok &= run_part(day.day, 2, solver.solve, &input, variant);
+    }
+    if !ok {
+        bail!("day {} failed", day.day);
+    }
+    Ok(())
+}
+
+fn main() -> Result<()> {
+    color_eyre::install()?;
+
+    let days = registry();
+    let args = Args::parse(std::env::args().skip(1))?;
+    let part = args.part.unwrap_or(Part::Both);
+    let answers = args.answers.as_deref().unwrap_or(verify::ANSWERS);
+
+    match args.positional.iter().map(String::as_str).collect::<Vec<_>>()[..] {
+        ["list"] => {
+            list(&days);
+            Ok(())
+        }
+        ["all"] => {
+            if let Some(flag) = args.single_day_flag() {
+                bail!("{flag} can only be used with a single day");
+            }
+            let failed: Vec<u8> = days
+                .iter()
+                .filter(|day| day.runnable())
+                .filter(|day| run_day(day, part, None, None).is_err())
+                .map(|day| day.day)
+                .collect();
+            if !failed.is_empty() {
+                bail!("failed days: {failed:?}");
+            }
+            Ok(())
+        }
+        ["bench"] | ["bench", "all"] => {
+            if let Some(flag) = args.single_day_flag() {
+                bail!("{flag} can only be used with a single day");
+            }
+            let selected: Vec<&Day> = days.iter().filter(|day| day.runnable()).collect();
+            bench::run(&selected, None, &args)
+        }
+        ["bench", target] => bench::run(&[find_day(&days, target)?], args.input.as_deref(), &args),
+        ["verify"] | ["verify", "all"] => {
+            if let Some(flag) = args.single_day_flag() {
+                bail!("{flag} can only be used with a single day");
+            }
+            let selected: Vec<&Day> = days.iter().filter(|day| day.runnable()).collect();
+            verify::run(&selected, answers, None, part, &args)
+        }
+        ["verify", target] => verify::run(&[find_day(&days, target)?], answers, args.input.as_deref(), part, &args),
+        [target] => run_day(find_day(&days, target)?, part, args.input.as_deref(), args.variant.as_deref()),
+        _ => bail!(usage()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    fn parse(args: &str) -> Result<Args> {
+        Args::parse(args.split_whitespace().map(String::from))
+    }
+
+    #[test]
+    fn test_args() {
+        let args = parse("verify 14 --part 2 --input - --record --variant flood --answers a.json").unwrap();
+        assert_eq!(vec!["verify", "14"], args.positional);
+        assert_eq!((Some(Part::Two), Some("-"), true), (args.part, args.input.as_deref(), args.record));
+        assert_eq!((Some("flood"), Some("a.json")), (args.variant.as_deref(), args.answers.as_deref()));
+        assert_eq!(Some("--input"), args.single_day_flag());
+
+        let args = parse("bench --runs 3 --json out.json").unwrap();
+        assert_eq!((Some(3), Some("out.json")), (args.runs, args.json.as_deref()));
+        assert_eq!(None, args.single_day_flag());
+    }
+
+    #[test_case("bench --runs 0" => "--runs needs a positive number" ; "zero runs")]
+    #[test_case("bench --runs x" => "--runs needs a positive number" ; "runs not a number")]
+    #[test_case("1 --part" => "--part needs a value" ; "missing value")]
+    #[test_case("1 --part 3" => "invalid part `3`, expected 1, 2 or both" ; "bad part")]
+    fn test_args_error(args: &str) -> String {
+        parse(args).unwrap_err().to_string()
+    }
+
+    #[test]
+    fn test_unknown_flag() {
+        assert!(parse("1 --fast").unwrap_err().to_string().starts_with("unexpected argument `--fast`\nusage: aoc"));
+    }
+}
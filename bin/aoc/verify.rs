@@ -0,0 +1,192 @@
+use crate::{Args, Day};
+use my_project1::runner::Part;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use serde_json::{json, Value};
+use std::panic;
+use std::path::Path;
+
+/// Answers file used unless `--answers` names another one.
+pub const ANSWERS: &str = "answers.json";
+
+/// A known-correct answer, keyed by day, part and input hash.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Entry {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: String,
+}
+
+impl Entry {
+    fn from_json(value: &Value) -> Option<Self> {
+        Some(Self {
+            day: value["day"].as_u64()?.try_into().ok()?,
+            part: value["part"].as_u64()?.try_into().ok()?,
+            input: value["input"].as_str()?.to_string(),
+            answer: value["answer"].as_str()?.to_string(),
+        })
+    }
+    fn to_json(&self) -> Value {
+        json!({ "day": self.day, "part": self.part, "input": self.input, "answer": self.answer })
+    }
+}
+
+/// What solving one part gave: the answer, or the error message if it failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct Solved {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: Result<String, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Outcome {
+    Pass,
+    /// Why the part failed: its error, or the expected and actual answers.
+    Fail(String),
+    /// No entry yet; holds the answer.
+    Missing(String),
+    /// No entry yet, so the answer was added.
+    Recorded(String),
+}
+
+fn load(path: &str) -> Result<Vec<Entry>> {
+    if !Path::new(path).exists() {
+        return Ok(Vec::new());
+    }
+    let text = std::fs::read_to_string(path).wrap_err_with(|| format!("failed to read {path}"))?;
+    let values: Vec<Value> = serde_json::from_str(&text).wrap_err_with(|| format!("failed to parse {path}"))?;
+    values
+        .iter()
+        .map(|value| Entry::from_json(value).ok_or_else(|| eyre!("invalid entry in {path}: {value}")))
+        .collect()
+}
+
+fn save(path: &str, entries: &mut [Entry]) -> Result<()> {
+    entries.sort_by(|a, b| (a.day, a.part, &a.input).cmp(&(b.day, b.part, &b.input)));
+    let values: Vec<Value> = entries.iter().map(Entry::to_json).collect();
+    let text = serde_json::to_string_pretty(&values)?;
+    std::fs::write(path, text + "\n").wrap_err_with(|| format!("failed to write {path}"))
+}
+
+/// Compares each solved part with its entry. With `record`, parts without one get
+/// their answer added to `entries`; existing entries are never changed.
+fn compare(entries: &mut Vec<Entry>, solved: &[Solved], record: bool) -> Vec<Outcome> {
+    solved.iter().map(|s| {
+        let expected = entries.iter().find(|e| e.day == s.day && e.part == s.part && e.input == s.input);
+        match (expected, &s.answer) {
+            (_, Err(err)) => Outcome::Fail(err.clone()),
+            (Some(expected), Ok(actual)) if expected.answer == *actual => Outcome::Pass,
+            (Some(expected), Ok(actual)) => Outcome::Fail(format!("expected {}, got {actual}", expected.answer)),
+            (None, Ok(actual)) if record => {
+                entries.push(Entry { day: s.day, part: s.part, input: s.input.clone(), answer: actual.clone() });
+                Outcome::Recorded(actual.clone())
+            }
+            (None, Ok(actual)) => Outcome::Missing(actual.clone()),
+        }
+    }).collect()
+}
+
+/// Solves the given days and compares each answer with the `answers` file, printing
+/// PASS, FAIL or MISSING per part. With `--record`, missing answers are added to the file.
+pub fn run(days: &[&Day], answers: &str, path: Option<&str>, part: Part, args: &Args) -> Result<()> {
+    let mut entries = load(answers)?;
+    let (mut passed, mut failed, mut missing, mut recorded) = (0, 0, 0, 0);
+
+    for day in days {
+        let Some(solver) = day.solver else {
+            bail!("day {} is not implemented yet", day.day);
+        };
+        let input = day.load_input(path)?;
+        let hash = my_project1::runner::input_hash(&input);
+        let solved: Vec<Solved> = (1..=2).filter(|&n| part.includes(n)).map(|n| {
+            let answer = panic::catch_unwind(|| (solver.solve)(&input, n, args.variant.as_deref())).unwrap_or_else(|_| Err(eyre!("panicked")));
+            Solved { day: day.day, part: n, input: hash.clone(), answer: answer.map_err(|err| format!("{err:#}")) }
+        }).collect();
+        for (s, outcome) in solved.iter().zip(compare(&mut entries, &solved, args.record)) {
+            match outcome {
+                Outcome::Pass => {
+                    println!("day {:>2} part {}  PASS", s.day, s.part);
+                    passed += 1;
+                }
+                Outcome::Fail(why) => {
+                    println!("day {:>2} part {}  FAIL     {why}", s.day, s.part);
+                    failed += 1;
+                }
+                Outcome::Missing(actual) => {
+                    println!("day {:>2} part {}  MISSING  {actual}", s.day, s.part);
+                    missing += 1;
+                }
+                Outcome::Recorded(actual) => {
+                    println!("day {:>2} part {}  RECORDED {actual}", s.day, s.part);
+                    recorded += 1;
+                }
+            }
+        }
+    }
+
+    if recorded > 0 {
+        save(answers, &mut entries)?;
+    }
+    println!("{passed} passed, {failed} failed, {missing} missing, {recorded} recorded");
+    if failed > 0 {
+        bail!("{failed} of {} answers did not match", passed + failed);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(day: u8, part: u8, answer: &str) -> Entry {
+        Entry { day, part, input: "abc".to_string(), answer: answer.to_string() }
+    }
+
+    fn solved_part(day: u8, part: u8, answer: Result<&str, &str>) -> Solved {
+        Solved { day, part, input: "abc".to_string(), answer: answer.map(String::from).map_err(String::from) }
+    }
+
+    #[test]
+    fn test_compare() {
+        let mut entries = vec![entry(1, 1, "24000"), entry(1, 2, "45000")];
+        let solved = [solved_part(1, 1, Ok("24000")), solved_part(1, 2, Ok("1")), solved_part(2, 1, Ok("15")), solved_part(2, 2, Err("no input"))];
+        assert_eq!(vec![
+            Outcome::Pass,
+            Outcome::Fail("expected 45000, got 1".to_string()),
+            Outcome::Missing("15".to_string()),
+            Outcome::Fail("no input".to_string()),
+        ], compare(&mut entries, &solved, false));
+        assert_eq!(2, entries.len());
+    }
+
+    #[test]
+    fn test_record() {
+        let mut entries = vec![entry(1, 1, "24000")];
+        let solved = [solved_part(1, 1, Ok("1")), solved_part(2, 1, Ok("15")), solved_part(2, 2, Err("no input"))];
+        assert_eq!(vec![
+            Outcome::Fail("expected 24000, got 1".to_string()),
+            Outcome::Recorded("15".to_string()),
+            Outcome::Fail("no input".to_string()),
+        ], compare(&mut entries, &solved, true));
+        assert_eq!(vec![entry(1, 1, "24000"), entry(2, 1, "15")], entries);
+
+        // A recorded answer is what later runs compare against.
+        assert_eq!(vec![Outcome::Fail("expected 15, got 16".to_string())], compare(&mut entries, &[solved_part(2, 1, Ok("16"))], true));
+        assert_eq!(2, entries.len());
+    }
+
+    #[test]
+    fn test_load_save() {
+        let path = std::env::temp_dir().join(format!("aoc-answers-{}.json", std::process::id()));
+        let path = path.to_str().unwrap();
+        assert_eq!(Vec::<Entry>::new(), load(path).unwrap());
+
+        let mut entries = vec![entry(2, 1, "15"), entry(1, 2, "45000"), entry(1, 1, "24000")];
+        save(path, &mut entries).unwrap();
+        let loaded = load(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        assert_eq!(vec![entry(1, 1, "24000"), entry(1, 2, "45000"), entry(2, 1, "15")], loaded);
+    }
+}
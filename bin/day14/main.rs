@@ -1,88 +1,55 @@
-use std::fmt;
-use itertools::Itertools;
+use std::fs::File;
+use std::io;
+use std::time::Duration;
+use color_eyre::eyre::{bail, Result, WrapErr};
+use my_project1::days::day14::{Day14, INPUT};
+use my_project1::{parse::normalize, runner::{self, Part, Solution}};
 
-#[derive(Debug, Clone)]
-struct Cave {
-    cave: Vec<Vec<char>>,
-    cmds: Vec<Vec<(usize, usize)>>,
-}
+/// Pause between animation frames unless `--delay` gives one in milliseconds.
+const FRAME_DELAY: Duration = Duration::from_millis(50);
 
-impl fmt::Display for Cave {
-    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        for row in &self.cave {
-            for c in row {
-                write!(f, "{}", c)?;
-            }
-            writeln!(f)?;
-        }
-        Ok(())
-    }
-}
-impl Cave {
-    fn new(x: usize, y: usize, cmds: Vec<Vec<(usize, usize)>>) -> Self {
-        Self {
-            cave: vec![vec!['.'; x]; y],
-            cmds,
-        }
-    }
-    fn populate(&mut self) {
-        self.cmds.iter().for_each(|line| {
-            line.iter().tuple_windows().for_each(|((x1, y1), (x2, y2))| {
-                if x1 == x2 {
-                    let (st_y, end_y) = if y1 <= y2 { (y1, y2) } else { (y2, y1) };
-                    for new_y in *st_y..=*end_y { self.cave[new_y][*x1] = '#' }
-                } else {
-                    let (st_x, end_x) = if x1 <= x2 { (x1, x2) } else { (x2, x1) };
-                    for new_x in *st_x..=*end_x { self.cave[*y1][new_x] = '#' }
-                }
-            })
-        })
-    }
-    fn simulate(&mut self, st_x: usize, max_y: usize, part2: bool) -> u32 {
-        for n in 0.. {
-            let (mut x, mut y) = (st_x, 0);
-            if self.cave[y][x] == 'O' && part2 { return n }
-            loop {
-                if y + 1 == max_y && !part2 { return n }
-                if self.cave[y + 1][x] == '.' { y += 1 }
-                else if self.cave[y + 1][x - 1] == '.' { y += 1; x -= 1 }
-                else if self.cave[y + 1][x + 1] == '.' { y += 1; x += 1 }
-                else { self.cave[y][x] = 'O'; break }
-            }
+/// Pours sand with `--part`'s rules (1 by default), printing a frame every
+/// `--animate` grains and writing the final cave to `--render`, a `.ppm` or
+/// `.png` path.
+fn show(animate: Option<String>, render: Option<String>) -> Result<()> {
+    color_eyre::install()?;
+    let part = runner::flag("--part").map_or(Ok(Part::One), |part| Part::parse(&part))?;
+    let delay = runner::flag("--delay").map(|ms| ms.parse().map(Duration::from_millis)).transpose()
+        .wrap_err("--delay needs a number of milliseconds")?
+        .unwrap_or(FRAME_DELAY);
+    let input = runner::load_input(runner::input_flag().as_deref(), INPUT)?;
+    let mut cave = Day14::parse(&normalize(&input))?;
+    let params = runner::params::<Day14>(runner::flag("--variant").as_deref())?;
+    let rules = match part {
+        Part::One => params.part1,
+        Part::Two => params.part2,
+        Part::Both => bail!("--animate and --render show one part, pass --part 1 or 2"),
+    };
+    let every = animate.map(|n| n.parse()).transpose().wrap_err("--animate needs a number of grains")?.unwrap_or(0);
+    let format = match render.as_deref().map(|path| path.rsplit_once('.').map_or("", |(_, ext)| ext)) {
+        Some(ext @ ("ppm" | "png")) => Some(ext),
+        Some(_) => bail!("--render needs a .ppm or .png path"),
+        None => None,
+    };
+    let rested = cave.animate(&rules, every, |cave, rested| if every > 0 {
+        println!("\x1b[H\x1b[2J{}{rested} grains", cave.render(&rules));
+        std::thread::sleep(delay);
+    });
+    if let (Some(path), Some(format)) = (&render, format) {
+        let file = io::BufWriter::new(File::create(path).wrap_err_with(|| format!("failed to create {path}"))?);
+        if format == "ppm" {
+            cave.write_ppm(&rules, 4, file)?;
+        } else {
+            cave.write_png(&rules, 4, file)?;
         }
-        0
     }
+    println!("{rested}");
+    Ok(())
 }
 
-fn parse() -> (Cave, usize, usize) {
-    let input = include_str!("input14.txt");
-
-    let (mut max_x, mut max_y) = (0, 0);
-
-    let grid = input.lines().map(|line| {
-        line.split(" -> ").map(|pair| {
-            let crd: Vec<usize> = pair.split(",").map(|x| x.parse::<usize>().unwrap()).collect();
-            max_x = max_x.max(crd[0]);
-            max_y = max_y.max(crd[1]);
-            (crd[0], crd[1])
-        }).collect::<Vec<(usize, usize)>>()
-    }).collect();
-    let cave = Cave::new(max_x + 500, max_y + 2, grid);
-    (cave, max_x, max_y)
-}
-
-fn part1() -> u32 {
-    let (mut cave, max_x, max_y) = parse();
-    cave.populate();
-    cave.simulate(500, max_y + 2, false)
-}
-
-fn part2() -> u32 {
-    let (mut cave, max_x, max_y) = parse();
-    cave.cave.push(vec!['#'; max_x + 500]);
-    cave.populate();
-    cave.simulate(500, max_y + 1, true)
+fn main() -> Result<()> {
+    match (runner::flag("--animate"), runner::flag("--render")) {
+        (None, None) => runner::run::<Day14>(INPUT),
+        (animate, render) => show(animate, render),
+    }
 }
-fn main() {
-    println!("{}", part1());
-}
\ No newline at end of file
@@ -1,5 +1,6 @@
+use color_eyre::eyre::Result;
+use my_project1::days::day18::{Day18, INPUT};
 
-fn main() {
-    let input = include_str!("input18.txt");
-    //println!("{}", part1(input));
-}
\ No newline at end of file
+fn main() -> Result<()> {
+    my_project1::runner::run::<Day18>(INPUT)
+}
@@ -1,5 +1,6 @@
+use color_eyre::eyre::Result;
+use my_project1::days::day19::{Day19, INPUT};
 
-fn main() {
-    let input = include_str!("input19.txt");
-    //println!("{}", part1(input));
-}
\ No newline at end of file
+fn main() -> Result<()> {
+    my_project1::runner::run::<Day19>(INPUT)
+}
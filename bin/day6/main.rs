@@ -1,13 +1,6 @@
-use itertools::Itertools;
+use color_eyre::eyre::Result;
+use my_project1::days::day6::{Day6, INPUT};
 
-fn part1(input: &str) -> usize {
-    input.as_bytes().windows(4).position(|win| win.iter().all_unique()).unwrap() + 4
+fn main() -> Result<()> {
+    my_project1::runner::run::<Day6>(INPUT)
 }
-
-fn part2(input: &str) -> usize {
-    input.as_bytes().windows(14).position(|win| win.iter().all_unique()).unwrap() + 14
-}
-fn main() {
-    let input = include_str!("input6.txt");
-    println!("{}", part2(input));
-}
\ No newline at end of file
@@ -0,0 +1,80 @@
+use std::fmt;
+use itertools::Itertools;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day1/input1.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Calories(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Calories(loc) => write!(f, "invalid calorie count at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Parsed<'a> = Vec<u32>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>> {
+        let elves = crate::parse::blocks(input)
+            .into_iter()
+            .map(|elf| {
+                elf.lines()
+                    .map(|s| s.trim().parse::<u32>().map_err(|_| ParseError::Calories(Location::of(input, s))))
+                    .sum::<Result<u32, _>>()
+            })
+            .collect::<Result<_, _>>()?;
+        Ok(elves)
+    }
+
+    fn part1(elves: &Vec<u32>, _: &()) -> u32 {
+        elves.iter().copied().max().unwrap_or(0)
+    }
+
+    fn part2(elves: &Vec<u32>, _: &()) -> u32 {
+        elves
+            .iter()
+            .sorted()
+            .rev()
+            .take(3)
+            .sum::<u32>()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day1/sample1.txt");
+
+    #[test_case(SAMPLE => 24000 ; "sample")]
+    fn test_part1(input: &str) -> u32 {
+        Day1::part1(&Day1::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 45000 ; "sample")]
+    fn test_part2(input: &str) -> u32 {
+        Day1::part2(&Day1::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day1::parse("1000\n\n20x0\n").unwrap_err();
+        assert_eq!("invalid calorie count at line 3, column 1: `20x0`", err.to_string());
+    }
+}
@@ -0,0 +1,184 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day10/input10.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Instr(Location),
+    Value(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Instr(loc) => write!(f, "unknown instruction at {loc}"),
+            ParseError::Value(loc) => write!(f, "invalid addx value at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy)]
+pub enum Instr {
+    Noop,
+    Addx(i32),
+}
+
+trait Cycle {
+    fn cycle(&mut self, instr: Instr);
+}
+
+#[derive(Debug, Clone)]
+struct Crt {
+    rgtr: i32,
+    cycle: i32,
+    output: String,
+}
+
+impl Crt {
+    fn new() -> Self {
+        Self {
+            rgtr: 1,
+            cycle: 0,
+            output: String::new(),
+        }
+    }
+    fn is_visible(&self) -> bool {
+        (-1..=1).any(|n| self.rgtr + n == self.cycle % 40)
+    }
+    fn new_line(&self) -> bool {
+        (self.cycle + 1) % 40 == 0
+    }
+    fn update(&mut self) {
+        self.output.push(if self.is_visible() { '#' } else { '.' });
+        if self.new_line() {self.output.push_str("\r\n")}
+    }
+}
+
+impl Cycle for Crt {
+    fn cycle(&mut self, instr: Instr) {
+        self.update();
+        match instr {
+            Instr::Addx(value) => {
+                self.cycle += 1;
+                self.update();
+                self.cycle += 1;
+                self.rgtr += value;
+            }
+            Instr::Noop => self.cycle += 1,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Cpu {
+    rgtr: i32,
+    cycle: i32,
+    output: Vec<i32>,
+}
+
+impl Cpu {
+    fn new() -> Self {
+        Self {
+            rgtr: 1,
+            cycle: 0,
+            output: Vec::new(),
+        }
+    }
+    fn find_signal_str(&self) -> i32 {
+        self.output.iter().take(6).sum()
+    }
+    fn update(&mut self) {
+        if (self.cycle - 20) % 40 == 0 {self.output.push(self.rgtr * self.cycle)}
+    }
+}
+
+impl Cycle for Cpu {
+    fn cycle(&mut self, instr: Instr) {
+        match instr {
+            Instr::Addx(value) => {
+                self.cycle += 1;
+                self.update();
+                self.cycle += 1;
+                self.update();
+                self.rgtr += value;
+            }
+            Instr::Noop => {
+                self.cycle += 1;
+                self.update();
+            }
+        }
+    }
+}
+
+fn cycle<T: Cycle>(device: &mut T, instr: Instr) {
+    device.cycle(instr);
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Parsed<'a> = Vec<Instr>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Vec<Instr>> {
+        let program = input.lines().map(|line| {
+            match line.split_once(' ') {
+                Some(("addx", value)) => value.parse().map(Instr::Addx).map_err(|_| ParseError::Value(Location::of(input, value))),
+                None if line == "noop" => Ok(Instr::Noop),
+                _ => Err(ParseError::Instr(Location::of(input, line))),
+            }
+        }).collect::<Result<_, _>>()?;
+        Ok(program)
+    }
+
+    fn part1(program: &Vec<Instr>, _: &()) -> i32 {
+        let mut cpu = Cpu::new();
+        program.iter().for_each(|&instr| cycle(&mut cpu, instr));
+        cpu.find_signal_str()
+    }
+
+    fn part2(program: &Vec<Instr>, _: &()) -> String {
+        let mut crt = Crt::new();
+        program.iter().for_each(|&instr| cycle(&mut crt, instr));
+        crt.output
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day10/sample10.txt");
+    const IMAGE: &str = concat!(
+        "##..##..##..##..##..##..##..##..##..##..\r\n",
+        "###...###...###...###...###...###...###.\r\n",
+        "####....####....####....####....####....\r\n",
+        "#####.....#####.....#####.....#####.....\r\n",
+        "######......######......######......####\r\n",
+        "#######.......#######.......#######.....\r\n",
+    );
+
+    #[test_case(SAMPLE => 13140 ; "sample")]
+    fn test_part1(input: &str) -> i32 {
+        Day10::part1(&Day10::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => IMAGE.to_string() ; "sample")]
+    fn test_part2(input: &str) -> String {
+        Day10::part2(&Day10::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day10::parse("noop\naddx 1x\n").unwrap_err();
+        assert_eq!("invalid addx value at line 2, column 6: `1x`", err.to_string());
+    }
+}
@@ -0,0 +1,204 @@
+use itertools::Itertools;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+use std::collections::VecDeque;
+use std::fmt;
+
+pub const INPUT: &str = include_str!("../../bin/day11/input11.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Missing(Location),
+    Field(Location),
+    Number(Location),
+    Operation(Location),
+    Target(Location),
+    Divisor(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Missing(loc) => write!(f, "monkey notes end early at {loc}"),
+            ParseError::Field(loc) => write!(f, "unexpected line in monkey notes at {loc}"),
+            ParseError::Number(loc) => write!(f, "invalid number at {loc}"),
+            ParseError::Operation(loc) => write!(f, "expected `* N`, `+ N` or `* old` at {loc}"),
+            ParseError::Target(loc) => write!(f, "no such monkey at {loc}"),
+            ParseError::Divisor(loc) => write!(f, "cannot test divisibility by zero at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy)]
+enum Op {
+    Mul(u64),
+    Add(u64),
+    Pow,
+}
+
+/// How worry levels are kept in check after each inspection.
+#[derive(Debug, Clone, Copy)]
+enum Relief {
+    /// Worry drops by this factor once the item is found undamaged.
+    Divide(u64),
+    /// No relief, so only the worry modulo the product of all tests is kept.
+    Modulo(u64),
+}
+
+#[derive(Debug, Clone)]
+struct Monkey {
+    items: VecDeque<u64>,
+    op: Op,
+    test: u64,
+    if_true: usize,
+    if_false: usize,
+    inspected: u64,
+}
+impl Monkey {
+    fn new(items: VecDeque<u64>, op: Op, test: u64, if_true: usize, if_false: usize) -> Self {
+        Monkey {
+            items,
+            op,
+            test,
+            if_true,
+            if_false,
+            inspected: 0,
+        }
+    }
+    fn adjust_worry(&self, worry: u64) -> u64 {
+        match self.op {
+            Op::Mul(x) => worry * x,
+            Op::Add(x) => worry + x,
+            Op::Pow => worry * worry,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Monkeys (Vec<Monkey>);
+
+impl Monkeys {
+    fn turn(&mut self, idx: usize, relief: Relief) {
+        let mnk = &mut self.0[idx];
+        let mut items = Vec::new();
+        while let Some(item) = mnk.items.pop_front() {
+            let mut new_item = mnk.adjust_worry(item);
+            match relief {
+                Relief::Divide(d) => new_item /= d,
+                Relief::Modulo(m) => new_item %= m,
+            }
+            mnk.inspected += 1;
+            let tg_idx = if new_item.is_multiple_of(mnk.test) {mnk.if_true} else {mnk.if_false};
+            items.push((new_item, tg_idx));
+        }
+        items.reverse();
+        while let Some((item, idx)) = items.pop() {
+            self.0[idx].items.push_back(item)
+        }
+    }
+    fn round(&mut self, relief: Relief) {
+        (0..self.0.len()).for_each(|id| self.turn(id, relief))
+    }
+    fn monkey_business(&self) -> u64 {
+        self.0.iter().map(|mnk| mnk.inspected).sorted_unstable_by(|a, b| b.cmp(a)).take(2).product()
+    }
+}
+
+fn field<'a>(input: &'a str, monkey: &'a str, line: Option<&'a str>, prefix: &str) -> Result<&'a str, ParseError> {
+    let line = line.ok_or_else(|| ParseError::Missing(Location::of(input, monkey)))?;
+    line.trim().strip_prefix(prefix).ok_or_else(|| ParseError::Field(Location::of(input, line)))
+}
+
+fn number<T: std::str::FromStr>(input: &str, s: &str) -> Result<T, ParseError> {
+    s.parse().map_err(|_| ParseError::Number(Location::of(input, s)))
+}
+
+fn parse_input(input: &str) -> Result<Monkeys, ParseError> {
+    let mut mnks = Vec::new();
+    let mut targets = Vec::new();
+    for monkey in crate::parse::blocks(input) {
+        let mut iter = monkey.lines().skip(1);
+        let items = field(input, monkey, iter.next(), "Starting items: ")?;
+        let itms: VecDeque<u64> = items.split(", ").map(|x| number(input, x)).collect::<Result<_, _>>()?;
+        let operation = field(input, monkey, iter.next(), "Operation: new = old ")?;
+        let op = match operation.split_once(' ') {
+            Some(("*", "old")) => Op::Pow,
+            Some(("*", num)) => Op::Mul(number(input, num)?),
+            Some(("+", num)) => Op::Add(number(input, num)?),
+            _ => return Err(ParseError::Operation(Location::of(input, operation))),
+        };
+        let divisor = field(input, monkey, iter.next(), "Test: divisible by ")?;
+        let test = number(input, divisor)?;
+        if test == 0 {
+            return Err(ParseError::Divisor(Location::of(input, divisor)));
+        }
+        let if_true = field(input, monkey, iter.next(), "If true: throw to monkey ")?;
+        let if_false = field(input, monkey, iter.next(), "If false: throw to monkey ")?;
+        targets.extend([if_true, if_false]);
+        let mnk = Monkey::new(itms, op, test, number(input, if_true)?, number(input, if_false)?);
+        mnks.push(mnk);
+    }
+    if let Some(target) = targets.into_iter().find(|t| t.parse::<usize>().is_ok_and(|idx| idx >= mnks.len())) {
+        return Err(ParseError::Target(Location::of(input, target)));
+    }
+    Ok(Monkeys(mnks))
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Parsed<'a> = Monkeys;
+    type Params = ();
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Monkeys> {
+        Ok(parse_input(input)?)
+    }
+
+    fn part1(mnks: &Monkeys, _: &()) -> u64 {
+        let mut mnks = mnks.clone();
+        for _ in 0..20 {mnks.round(Relief::Divide(3))}
+        mnks.monkey_business()
+    }
+
+    fn part2(mnks: &Monkeys, _: &()) -> u64 {
+        let mut mnks = mnks.clone();
+        let d: u64 = mnks.0.iter()
+            .map(|mnk| mnk.test)
+            .product();
+        for _ in 0..10000 {mnks.round(Relief::Modulo(d))}
+        mnks.monkey_business()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day11/sample11.txt");
+
+    #[test_case(SAMPLE => 10605 ; "sample")]
+    fn test_part1(input: &str) -> u64 {
+        Day11::part1(&Day11::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 2713310158 ; "sample")]
+    fn test_part2(input: &str) -> u64 {
+        Day11::part2(&Day11::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let notes = SAMPLE.replace("throw to monkey 3", "throw to monkey 4");
+        let err = Day11::parse(&notes).unwrap_err();
+        assert_eq!("no such monkey at line 6, column 31: `4`", err.to_string());
+        let notes = SAMPLE.replacen("divisible by 23", "divisible by 0", 1);
+        let err = Day11::parse(&notes).unwrap_err();
+        assert_eq!("cannot test divisibility by zero at line 4, column 22: `0`", err.to_string());
+    }
+}
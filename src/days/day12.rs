@@ -0,0 +1,107 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{grid::{Grid, GridError}, parse::Location, runner::Solution, search::bfs};
+
+pub const INPUT: &str = include_str!("../../bin/day12/input12.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Height(Location),
+    Width(Location),
+    Start,
+    End,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Height(loc) => write!(f, "expected a lowercase letter, `S` or `E` at {loc}"),
+            ParseError::Width(loc) => write!(f, "rows must all have the same length at {loc}"),
+            ParseError::Start => write!(f, "missing start `S`"),
+            ParseError::End => write!(f, "missing end `E`"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug)]
+pub struct Heightmap {
+    map: Grid<u8>,
+    start: (usize, usize),
+    end: (usize, usize),
+}
+
+impl Heightmap {
+    fn lowest_points(&self) -> Vec<(usize, usize)> {
+        self.map.positions().filter(|&pos| self.map[pos] == 1).collect()
+    }
+    fn find_path(&self, st_pts: &[(usize, usize)]) -> u32 {
+        let climbable = |&(x, y): &(usize, usize)| {
+            let h = self.map[(x, y)];
+            self.map.neighbours(x, y).filter(move |&next| self.map[next] - 1 <= h)
+        };
+        bfs(st_pts.iter().copied(), climbable, |&pos| pos == self.end).map_or(0, |steps| steps as u32)
+    }
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Parsed<'a> = Heightmap;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Heightmap> {
+        let mut start = None;
+        let mut end = None;
+        let grid = Grid::parse(input, |x, y, c| match c {
+            'S' => { start = Some((x, y)); Some(1) },
+            'E' => { end = Some((x, y)); Some(26) },
+            'a'..='z' => Some((c as u8) - b'a' + 1),
+            _ => None,
+        }).map_err(|err| match err {
+            GridError::Cell(c) => ParseError::Height(Location::of(input, c)),
+            GridError::Width(line) => ParseError::Width(Location::of(input, line)),
+        })?;
+        let start = start.ok_or(ParseError::Start)?;
+        let end = end.ok_or(ParseError::End)?;
+        Ok(Heightmap { map: grid, start, end })
+    }
+
+    fn part1(hgtmap: &Heightmap, _: &()) -> u32 {
+        hgtmap.find_path(&[hgtmap.start])
+    }
+
+    fn part2(hgtmap: &Heightmap, _: &()) -> u32 {
+        hgtmap.find_path(&hgtmap.lowest_points())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day12/sample12.txt");
+
+    #[test_case(SAMPLE => 31 ; "sample")]
+    fn test_part1(input: &str) -> u32 {
+        Day12::part1(&Day12::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 29 ; "sample")]
+    fn test_part2(input: &str) -> u32 {
+        Day12::part2(&Day12::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day12::parse("Sab\naB E\n").unwrap_err();
+        assert_eq!("expected a lowercase letter, `S` or `E` at line 2, column 2: `B`", err.to_string());
+        assert_eq!("missing start `S`", Day12::parse("abE\n").unwrap_err().to_string());
+        assert_eq!("missing end `E`", Day12::parse("Sab\n").unwrap_err().to_string());
+    }
+}
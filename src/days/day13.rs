@@ -0,0 +1,360 @@
+use itertools::Itertools;
+use serde_json::Value;
+use std::cmp::Ordering;
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+use nom::{
+    IResult,
+    branch::alt,
+    character::complete::{char, digit1},
+    combinator::{cut, map, opt},
+    error::ErrorKind,
+    multi::many0,
+    sequence::{delimited, pair, preceded},
+};
+
+pub const INPUT: &str = include_str!("../../bin/day13/input13.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Pair(Location),
+    Packet(Location),
+    Number(Location),
+    Unclosed(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Pair(loc) => write!(f, "expected two packets at {loc}"),
+            ParseError::Packet(loc) => write!(f, "unexpected character in packet at {loc}"),
+            ParseError::Number(loc) => write!(f, "number out of range at {loc}"),
+            ParseError::Unclosed(loc) => write!(f, "unclosed list at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum Packet {
+    Num(u32),
+    List(Vec<Packet>),
+}
+
+impl Ord for Packet {
+    fn cmp(&self, other: &Self) -> Ordering {
+        match (self, other) {
+            (Self::List(a), Self::List(b)) => a.cmp(b),
+            (Self::List(a), Self::Num(b)) => a.cmp(&vec![Self::Num(*b)]),
+            (Self::Num(a), Self::List(b)) => vec![Self::Num(*a)].cmp(b),
+            (Self::Num(a), Self::Num(b)) => a.cmp(b),
+        }
+    }
+}
+
+impl PartialOrd for Packet {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl fmt::Display for Packet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Packet::Num(n) => write!(f, "{n}"),
+            Packet::List(items) => write!(f, "[{}]", items.iter().join(",")),
+        }
+    }
+}
+
+/// Digits that don't fit a `u32` fail outright instead of letting `alt` backtrack.
+fn number(input: &str) -> IResult<&str, u32> {
+    let (rest, digits) = digit1(input)?;
+    match digits.parse() {
+        Ok(n) => Ok((rest, n)),
+        Err(_) => Err(nom::Err::Failure(nom::error::Error::new(digits, ErrorKind::MapRes))),
+    }
+}
+
+fn packet(input: &str) -> IResult<&str, Packet> {
+    alt((map(number, Packet::Num), list))(input)
+}
+
+fn list(input: &str) -> IResult<&str, Packet> {
+    // Once a `,` is read another packet has to follow, so errors point at it.
+    let items = map(opt(pair(packet, many0(preceded(char(','), cut(packet))))), |items| {
+        items.map_or_else(Vec::new, |(first, rest)| std::iter::once(first).chain(rest).collect())
+    });
+    map(delimited(char('['), items, char(']')), Packet::List)(input)
+}
+
+/// A JSON value that has no packet equivalent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NotAPacket(pub Value);
+
+impl fmt::Display for NotAPacket {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "`{}` is not a packet, expected an array or an integer from 0 to {}", self.0, u32::MAX)
+    }
+}
+
+impl std::error::Error for NotAPacket {}
+
+impl From<&Packet> for Value {
+    fn from(packet: &Packet) -> Self {
+        match packet {
+            Packet::Num(n) => Value::from(*n),
+            Packet::List(items) => Value::Array(items.iter().map(Value::from).collect()),
+        }
+    }
+}
+
+impl TryFrom<&Value> for Packet {
+    type Error = NotAPacket;
+
+    fn try_from(value: &Value) -> Result<Self, NotAPacket> {
+        match value {
+            Value::Number(n) => n.as_u64().and_then(|n| n.try_into().ok()).map(Packet::Num).ok_or_else(|| NotAPacket(value.clone())),
+            Value::Array(items) => items.iter().map(Packet::try_from).collect::<Result<_, _>>().map(Packet::List),
+            _ => Err(NotAPacket(value.clone())),
+        }
+    }
+}
+
+impl Packet {
+    /// Reads a packet from JSON text, e.g. a file holding an array of packets.
+    pub fn from_json(text: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(text)?;
+        Ok(Packet::try_from(&value)?)
+    }
+    pub fn to_json(&self) -> String {
+        Value::from(self).to_string()
+    }
+}
+
+impl Packet {
+    /// Compares like `cmp`, also recording in `path` the list indices leading to
+    /// the element that decided the order. Nothing is recorded for equal packets.
+    fn compare(&self, other: &Self, path: &mut Vec<usize>) -> Ordering {
+        match (self, other) {
+            (Self::Num(a), Self::Num(b)) => a.cmp(b),
+            (Self::List(a), Self::List(b)) => {
+                for (idx, (x, y)) in a.iter().zip(b).enumerate() {
+                    path.push(idx);
+                    match x.compare(y, path) {
+                        Ordering::Equal => { path.pop(); }
+                        ord => return ord,
+                    }
+                }
+                if a.len() != b.len() {
+                    path.push(a.len().min(b.len()));
+                }
+                a.len().cmp(&b.len())
+            }
+            (Self::Num(a), list) => Self::List(vec![Self::Num(*a)]).compare(list, path),
+            (list, Self::Num(b)) => list.compare(&Self::List(vec![Self::Num(*b)]), path),
+        }
+    }
+    /// The ordering of two packets plus the path of list indices to the first
+    /// differing element; an index equal to a list's length means it ran out.
+    pub fn explain(&self, other: &Self) -> (Ordering, Vec<usize>) {
+        let mut path = Vec::new();
+        (self.compare(other, &mut path), path)
+    }
+}
+
+/// The 1-based number and first differing path of each pair not in the right order.
+pub fn out_of_order(pairs: &[[Packet; 2]]) -> Vec<(usize, Vec<usize>)> {
+    pairs.iter().enumerate().filter_map(|(idx, [left, right])| match left.explain(right) {
+        (Ordering::Less, _) => None,
+        (_, path) => Some((idx + 1, path)),
+    }).collect()
+}
+
+/// Where each divider ends up, 1-based, once it is sorted together with `packets`
+/// and the other dividers, found by counting the packets that sort before it.
+/// Dividers go ahead of packets equal to them and keep their relative order.
+pub fn divider_indices<'a>(packets: impl IntoIterator<Item = &'a Packet>, dividers: &[Packet]) -> Vec<usize> {
+    let mut indices: Vec<usize> = (0..dividers.len())
+        .map(|i| 1 + dividers.iter().enumerate().filter(|&(j, d)| d < &dividers[i] || (j < i && d == &dividers[i])).count())
+        .collect();
+    for packet in packets {
+        for (idx, divider) in indices.iter_mut().zip(dividers) {
+            if packet < divider {
+                *idx += 1;
+            }
+        }
+    }
+    indices
+}
+
+fn first_char(s: &str) -> &str {
+    &s[..s.chars().next().map_or(0, char::len_utf8)]
+}
+
+impl Packet {
+    /// Parses one packet, which must be a list, from `line`, a line of `input`.
+    fn parse_line(input: &str, line: &str) -> Result<Self, ParseError> {
+        match list(line) {
+            Ok(("", packet)) => Ok(packet),
+            Ok((rest, _)) => Err(ParseError::Packet(Location::of(input, first_char(rest)))),
+            Err(nom::Err::Error(e) | nom::Err::Failure(e)) => Err(match e.code {
+                ErrorKind::MapRes => ParseError::Number(Location::of(input, e.input)),
+                _ if e.input.is_empty() => ParseError::Unclosed(Location::of(input, e.input)),
+                _ => ParseError::Packet(Location::of(input, first_char(e.input))),
+            }),
+            Err(nom::Err::Incomplete(_)) => Err(ParseError::Unclosed(Location::of(input, &line[line.len()..]))),
+        }
+    }
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Parsed<'a> = Vec<[Packet; 2]>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<[Packet; 2]>> {
+        let pairs = crate::parse::blocks(input).into_iter().map(|pair| {
+            let (left, right) = pair.lines().collect_tuple().ok_or_else(|| ParseError::Pair(Location::of(input, pair)))?;
+            Ok([Packet::parse_line(input, left)?, Packet::parse_line(input, right)?])
+        }).collect::<Result<_, ParseError>>()?;
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Vec<[Packet; 2]>, _: &()) -> usize {
+        pairs
+        .iter()
+        .positions(|[left, right]| left < right)
+        .map(|idx| idx + 1)
+        .sum()
+    }
+
+    fn part2(pairs: &Vec<[Packet; 2]>, _: &()) -> usize {
+        let dividers = ["[[2]]", "[[6]]"].map(|div| Packet::parse_line(div, div).expect("divider packets are well-formed"));
+        divider_indices(pairs.iter().flatten(), &dividers).into_iter().product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::test_runner::FileFailurePersistence;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day13/sample13.txt");
+
+    #[test_case(SAMPLE => 13 ; "sample")]
+    fn test_part1(input: &str) -> usize {
+        Day13::part1(&Day13::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 140 ; "sample")]
+    fn test_part2(input: &str) -> usize {
+        Day13::part2(&Day13::parse(input).unwrap(), &())
+    }
+
+    fn arb_packet() -> impl Strategy<Value = Packet> {
+        let leaf = any::<u32>().prop_map(Packet::Num);
+        leaf.prop_recursive(4, 64, 6, |inner| prop::collection::vec(inner, 0..6).prop_map(Packet::List))
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            failure_persistence: Some(Box::new(FileFailurePersistence::WithSource("regressions"))),
+            ..ProptestConfig::default()
+        })]
+
+        #[test]
+        fn test_display_round_trip(items in prop::collection::vec(arb_packet(), 0..6)) {
+            let packet = Packet::List(items);
+            let text = packet.to_string();
+            prop_assert_eq!(packet, Packet::parse_line(&text, &text).unwrap());
+        }
+
+        #[test]
+        fn test_json_round_trip(packet in arb_packet()) {
+            prop_assert_eq!(&packet, &Packet::try_from(&Value::from(&packet)).unwrap());
+            prop_assert_eq!(&packet, &Packet::from_json(&packet.to_json()).unwrap());
+        }
+
+        #[test]
+        fn test_explain_matches_cmp(left in prop::collection::vec(arb_packet(), 0..6), right in prop::collection::vec(arb_packet(), 0..6)) {
+            let (left, right) = (Packet::List(left), Packet::List(right));
+            let (ord, path) = left.explain(&right);
+            prop_assert_eq!(left.cmp(&right), ord);
+            prop_assert_eq!(ord == Ordering::Equal, path.is_empty());
+        }
+
+        #[test]
+        fn test_divider_indices_match_sort(packets in prop::collection::vec(arb_packet(), 0..12), dividers in prop::collection::vec(arb_packet(), 0..4)) {
+            let mut sorted: Vec<(&Packet, Option<usize>)> = packets.iter().map(|p| (p, None)).chain(dividers.iter().enumerate().map(|(i, d)| (d, Some(i)))).collect();
+            sorted.sort_by(|a, b| a.0.cmp(b.0).then(a.1.is_none().cmp(&b.1.is_none())).then(a.1.cmp(&b.1)));
+            let mut expected = vec![0; dividers.len()];
+            for (pos, (_, div)) in sorted.iter().enumerate() {
+                if let Some(i) = div { expected[*i] = pos + 1 }
+            }
+            prop_assert_eq!(expected, divider_indices(&packets, &dividers));
+        }
+
+        #[test]
+        fn test_parse_never_panics(line in "[\\[\\],0-9x]{0,16}") {
+            if let Ok(packet) = Packet::parse_line(&line, &line) {
+                prop_assert_eq!(line, packet.to_string());
+            }
+        }
+    }
+
+    #[test]
+    fn test_out_of_order() {
+        let pairs = Day13::parse(SAMPLE).unwrap();
+        assert_eq!(vec![(3, vec![0, 0]), (5, vec![3]), (7, vec![0, 0]), (8, vec![1, 1, 1, 1, 2])], out_of_order(&pairs));
+        assert_eq!((Ordering::Less, vec![1, 0]), pairs[1][0].explain(&pairs[1][1]));
+        assert_eq!((Ordering::Equal, vec![]), pairs[0][0].explain(&pairs[0][0]));
+    }
+
+    #[test]
+    fn test_divider_indices() {
+        let packets = Day13::parse(SAMPLE).unwrap();
+        let dividers = ["[[2]]", "[[6]]", "[]", "[[2]]"].map(|d| Packet::parse_line(d, d).unwrap());
+        assert_eq!(vec![10, 14], divider_indices(packets.iter().flatten(), &dividers[..2]));
+        assert_eq!(vec![11, 16, 1, 12], divider_indices(packets.iter().flatten(), &dividers));
+        assert_eq!(Vec::<usize>::new(), divider_indices(packets.iter().flatten(), &[]));
+    }
+
+    #[test]
+    fn test_json() {
+        let pairs = Day13::parse(SAMPLE).unwrap();
+        let json = Value::Array(pairs.iter().flatten().map(Value::from).collect()).to_string();
+        let Packet::List(packets) = Packet::from_json(&json).unwrap() else { panic!("expected a list") };
+        assert_eq!(pairs.iter().flatten().cloned().collect::<Vec<_>>(), packets);
+        assert!(packets[0] < packets[1]);
+        assert_eq!("[[1],[2,3,4]]", Packet::from_json(" [ [1], [2, 3, 4] ] ").unwrap().to_json());
+
+        assert_eq!("`-1` is not a packet, expected an array or an integer from 0 to 4294967295", Packet::from_json("[1,[-1]]").unwrap_err().to_string());
+        assert!(Packet::from_json("{\"a\": 1}").is_err());
+        assert!(Packet::from_json("[1.5]").is_err());
+        assert!(Packet::from_json("[4294967296]").is_err());
+        assert!(Packet::from_json("[1,").is_err());
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day13::parse("[1,[2]]\n[1,[2,x]]\n").unwrap_err();
+        assert_eq!("unexpected character in packet at line 2, column 7: `x`", err.to_string());
+        let err = Day13::parse("[1,[2]]\n[1,[2]\n").unwrap_err();
+        assert_eq!("unclosed list at line 2, column 7: ``", err.to_string());
+        let err = Day13::parse("[1]]\n[2]\n").unwrap_err();
+        assert_eq!("unexpected character in packet at line 1, column 4: `]`", err.to_string());
+        let err = Day13::parse("[1]\n[[99999999999]]\n").unwrap_err();
+        assert_eq!("number out of range at line 2, column 3: `99999999999`", err.to_string());
+        let err = Day13::parse("[1]\n7\n").unwrap_err();
+        assert_eq!("unexpected character in packet at line 2, column 1: `7`", err.to_string());
+    }
+}
@@ -0,0 +1,590 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::io;
+use itertools::Itertools;
+use color_eyre::eyre::Result;
+use crate::{geom::Point, grid::Grid, parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day14/input14.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Point(Location),
+    Coordinate(Location),
+    /// A segment of the path on `line`, counting from 1, is neither horizontal nor vertical.
+    Diagonal { line: usize, from: Point, to: Point },
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Point(loc) => write!(f, "expected a point like `498,4` at {loc}"),
+            ParseError::Coordinate(loc) => write!(f, "invalid coordinate at {loc}"),
+            ParseError::Diagonal { line, from, to } => write!(
+                f, "rock paths must be horizontal or vertical, but line {line} goes from {},{} to {},{}", from.x, from.y, to.x, to.y,
+            ),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// What fills a cell of the cave; anything absent is air.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Tile {
+    Rock,
+    Sand,
+}
+
+/// The cave as a sparse map from position to tile, so rocks and sand can sit
+/// anywhere, left of zero included. The bounds grow as sand comes to rest, and
+/// are `None` while the cave is empty.
+#[derive(Debug, Clone, Default)]
+pub struct Cave {
+    tiles: HashMap<Point, Tile>,
+    rock: Option<(Point, Point)>,
+    bounds: Option<(Point, Point)>,
+    steps: u64,
+}
+
+impl fmt::Display for Cave {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some((min, max)) = self.bounds else { return Ok(()) };
+        for y in min.y..=max.y {
+            for x in min.x..=max.x {
+                let c = match self.get(Point::new(x, y)) {
+                    Some(Tile::Rock) => '#',
+                    Some(Tile::Sand) => 'o',
+                    None => '.',
+                };
+                write!(f, "{c}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl Cave {
+    /// Lays rock along each path of horizontal and vertical segments.
+    pub fn new(paths: &[Vec<Point>]) -> Result<Self, ParseError> {
+        let mut cave = Self::default();
+        for (idx, path) in paths.iter().enumerate() {
+            for (&from, &to) in path.iter().tuple_windows() {
+                if from.x != to.x && from.y != to.y {
+                    return Err(ParseError::Diagonal { line: idx + 1, from, to });
+                }
+                cave.lay_rock(from, to);
+            }
+        }
+        cave.rock = paths.iter().flatten().map(|&p| (p, p)).reduce(|(min, max), (p, _)| (min.min(p), max.max(p)));
+        cave.bounds = cave.rock;
+        Ok(cave)
+    }
+    fn lay_rock(&mut self, from: Point, to: Point) {
+        let step = (to - from).signum();
+        let mut p = from;
+        self.tiles.insert(p, Tile::Rock);
+        while p != to {
+            p += step;
+            self.tiles.insert(p, Tile::Rock);
+        }
+    }
+    pub fn get(&self, p: Point) -> Option<Tile> {
+        self.tiles.get(&p).copied()
+    }
+    /// Corners of the smallest rectangle holding every rock, if there is any.
+    pub fn rock_bounds(&self) -> Option<(Point, Point)> {
+        self.rock
+    }
+    /// Corners of the smallest rectangle holding every rock and grain of sand.
+    pub fn bounds(&self) -> Option<(Point, Point)> {
+        self.bounds
+    }
+    /// Depth of a floor `offset` below the lowest rock. A cave without rock has
+    /// nothing for a floor to lie under, so it has none.
+    fn floor(&self, offset: Option<i64>) -> Option<i64> {
+        Some(self.rock?.1.y + offset?)
+    }
+    pub fn sand(&self) -> usize {
+        self.tiles.values().filter(|&&tile| tile == Tile::Sand).count()
+    }
+    fn is_free(&self, p: Point, floor: Option<i64>) -> bool {
+        !self.tiles.contains_key(&p) && floor.is_none_or(|floor| p.y < floor)
+    }
+    /// How many one-cell moves grains have made so far.
+    pub fn steps(&self) -> u64 {
+        self.steps
+    }
+    fn settle(&mut self, p: Point) {
+        self.tiles.insert(p, Tile::Sand);
+        self.bounds = Some(self.bounds.map_or((p, p), |(min, max)| (min.min(p), max.max(p))));
+    }
+    /// Drops one grain from `source` and follows it until it rests, finds the
+    /// source blocked or, with no `floor`, falls past every rock.
+    pub fn drop_sand(&mut self, source: Point, floor: Option<i64>) -> Grain {
+        self.pour(source, floor, &mut Vec::new())
+    }
+    /// Like `drop_sand`, but the grain starts from the end of `path`, the fall of
+    /// the previous grain from `source`, once the cells since filled are dropped
+    /// from it. Everything above there is the same for every grain, so the result
+    /// matches starting from `source`.
+    fn pour(&mut self, source: Point, floor: Option<i64>, path: &mut Vec<Point>) -> Grain {
+        while path.last().is_some_and(|&p| !self.is_free(p, floor)) {
+            path.pop();
+        }
+        if path.is_empty() {
+            if !self.is_free(source, floor) { return Grain::Blocked }
+            path.push(source);
+        }
+        let mut p = path[path.len() - 1];
+        loop {
+            if floor.is_none() && self.rock.is_none_or(|(_, max)| p.y > max.y) { return Grain::Fell }
+            match [0, -1, 1].into_iter().map(|dx| p + Point::new(dx, 1)).find(|&n| self.is_free(n, floor)) {
+                Some(next) => { p = next; path.push(p); self.steps += 1 }
+                None => break,
+            }
+        }
+        self.settle(p);
+        Grain::Rested(p)
+    }
+    /// Pours sand under `rules`, one grain per active source per round, until
+    /// every source is done. Returns how many grains came to rest.
+    pub fn simulate(&mut self, rules: &SandRules) -> u32 {
+        self.animate(rules, 0, |_, _| {})
+    }
+    /// Like `simulate`, but hands the cave and the grains at rest so far to `frame`
+    /// after every `every` grains (never if `0`) and once more at the end.
+    pub fn animate(&mut self, rules: &SandRules, every: u32, mut frame: impl FnMut(&Cave, u32)) -> u32 {
+        let floor = self.floor(rules.floor);
+        let mut active: Vec<(Point, Vec<Point>)> = rules.sources.iter().map(|&source| (source, Vec::new())).collect();
+        let mut rested = 0;
+        'pour: while !active.is_empty() {
+            let mut idx = 0;
+            while idx < active.len() {
+                let (source, path) = &mut active[idx];
+                if !rules.resume {
+                    path.clear();
+                }
+                match self.pour(*source, floor, path) {
+                    Grain::Rested(_) => { rested += 1; idx += 1 }
+                    Grain::Fell if rules.stop_at_abyss => break 'pour,
+                    Grain::Fell | Grain::Blocked => { active.remove(idx); continue }
+                }
+                if every > 0 && rested % every == 0 {
+                    frame(self, rested);
+                }
+            }
+        }
+        // Unless the last grain already showed this state.
+        if every == 0 || rested == 0 || rested % every != 0 {
+            frame(self, rested);
+        }
+        rested
+    }
+}
+
+const AIR: [u8; 3] = [0x0f, 0x0f, 0x23];
+const ROCK: [u8; 3] = [0x70, 0x70, 0x70];
+const SAND: [u8; 3] = [0xe8, 0xc5, 0x6a];
+const SOURCE: [u8; 3] = [0xff, 0xff, 0xff];
+
+impl Cave {
+    /// The cave cropped to its rock and sand plus the sources (`+`) and floor of
+    /// `rules`, one character per cell.
+    fn canvas(&self, rules: &SandRules) -> Grid<char> {
+        let points = self.bounds.into_iter().flat_map(|(min, max)| [min, max]).chain(rules.sources.iter().copied());
+        let (mut min, mut max) = points.map(|p| (p, p)).reduce(|(min, max), (p, _)| (min.min(p), max.max(p))).unwrap_or_default();
+        let floor = self.floor(rules.floor);
+        if let Some(floor) = floor {
+            (min.y, max.y) = (min.y.min(floor), max.y.max(floor));
+        }
+        let mut canvas = Grid::new((max.x - min.x + 1) as usize, (max.y - min.y + 1) as usize, '.');
+        let cell = |p: Point| ((p.x - min.x) as usize, (p.y - min.y) as usize);
+        for (&p, tile) in &self.tiles {
+            canvas[cell(p)] = if *tile == Tile::Rock { '#' } else { 'o' };
+        }
+        for &p in &rules.sources {
+            if canvas[cell(p)] == '.' { canvas[cell(p)] = '+' }
+        }
+        if let Some(floor) = floor {
+            (min.x..=max.x).for_each(|x| canvas[cell(Point::new(x, floor))] = '#');
+        }
+        canvas
+    }
+    /// Text picture of the cave as `canvas` draws it, in the puzzle's style.
+    pub fn render(&self, rules: &SandRules) -> String {
+        self.canvas(rules).rows().map(|row| row.iter().chain(['\n'].iter()).collect::<String>()).collect()
+    }
+    /// RGB bytes of the picture with each cell drawn `scale` pixels wide, plus
+    /// its width and height in pixels.
+    fn pixels(&self, rules: &SandRules, scale: usize) -> (usize, usize, Vec<u8>) {
+        let (canvas, scale) = (self.canvas(rules), scale.max(1));
+        let pixels = canvas.rows().flat_map(|row| {
+            let line: Vec<u8> = row.iter().flat_map(|c| match c {
+                '#' => ROCK,
+                'o' => SAND,
+                '+' => SOURCE,
+                _ => AIR,
+            }.repeat(scale)).collect();
+            std::iter::repeat_n(line, scale).flatten()
+        }).collect();
+        (canvas.width() * scale, canvas.height() * scale, pixels)
+    }
+    /// Writes the picture as a binary PPM image.
+    pub fn write_ppm(&self, rules: &SandRules, scale: usize, mut out: impl io::Write) -> io::Result<()> {
+        let (width, height, pixels) = self.pixels(rules, scale);
+        write!(out, "P6\n{width} {height}\n255\n")?;
+        out.write_all(&pixels)
+    }
+    /// Writes the picture as a PNG image.
+    pub fn write_png(&self, rules: &SandRules, scale: usize, out: impl io::Write) -> io::Result<()> {
+        let (width, height, pixels) = self.pixels(rules, scale);
+        let mut encoder = png::Encoder::new(out, width as u32, height as u32);
+        encoder.set_color(png::ColorType::Rgb);
+        encoder.set_depth(png::BitDepth::Eight);
+        encoder.write_header().and_then(|mut writer| writer.write_image_data(&pixels)).map_err(io::Error::other)
+    }
+}
+
+/// What became of one grain of sand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Grain {
+    Rested(Point),
+    /// The source itself was covered, so nothing fell.
+    Blocked,
+    /// It fell past the lowest rock into the abyss.
+    Fell,
+}
+
+/// How sand enters the cave and what stops it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SandRules {
+    /// Where grains are poured from, taking turns.
+    pub sources: Vec<Point>,
+    /// Depth of an endless floor below the lowest rock, or `None` for an abyss.
+    pub floor: Option<i64>,
+    /// Whether the first grain lost to the abyss ends the simulation; otherwise
+    /// only its source stops pouring.
+    pub stop_at_abyss: bool,
+    /// Whether each grain resumes from where the previous grain of its source
+    /// left the open path, rather than falling all the way from the source.
+    pub resume: bool,
+}
+
+impl SandRules {
+    pub fn new(source: Point, floor: Option<i64>) -> Self {
+        Self { sources: vec![source], floor, stop_at_abyss: true, resume: false }
+    }
+    /// Runs a copy of `cave` and returns the grains at rest with the final cave.
+    pub fn run(&self, cave: &Cave) -> (u32, Cave) {
+        let mut cave = cave.clone();
+        (cave.simulate(self), cave)
+    }
+    /// Same result as `run` for a cave with a floor, without following single
+    /// grains: going down row by row, a free cell fills with sand if any of the
+    /// three cells above it did. `None` if there is no floor to stop the sand.
+    pub fn flood(&self, cave: &Cave) -> Option<(u32, Cave)> {
+        let floor = cave.floor(self.floor)?;
+        let top = self.sources.iter().map(|p| p.y).min()?;
+        let mut filled = cave.clone();
+        let (mut row, mut count) = (HashSet::new(), 0);
+        for y in top..floor {
+            row = row.iter().flat_map(|&x| [x - 1, x, x + 1]).chain(self.sources.iter().filter(|p| p.y == y).map(|p| p.x))
+                .filter(|&x| cave.get(Point::new(x, y)).is_none())
+                .collect();
+            for &x in &row {
+                filled.settle(Point::new(x, y));
+            }
+            count += row.len() as u32;
+        }
+        Some((count, filled))
+    }
+}
+
+/// How part 2 pours its sand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Method {
+    /// One grain at a time, following each grain's fall.
+    #[default]
+    Simulate,
+    /// The whole pile at once with `SandRules::flood`.
+    Flood,
+}
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub part1: SandRules,
+    pub part2: SandRules,
+    pub method: Method,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            part1: SandRules::new(Point::new(500, 0), None),
+            part2: SandRules::new(Point::new(500, 0), Some(2)),
+            method: Method::default(),
+        }
+    }
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Parsed<'a> = Cave;
+    type Params = Params;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Cave> {
+        let paths = input.lines().map(|line| {
+            line.split(" -> ").map(|pair| {
+                let (x, y) = pair.split_once(',').ok_or_else(|| ParseError::Point(Location::of(input, pair)))?;
+                let coord = |c: &str| c.parse::<i64>().map_err(|_| ParseError::Coordinate(Location::of(input, c)));
+                Ok(Point::new(coord(x)?, coord(y)?))
+            }).collect::<Result<Vec<Point>, ParseError>>()
+        }).collect::<Result<Vec<_>, _>>()?;
+        Ok(Cave::new(&paths)?)
+    }
+
+    fn part1(cave: &Cave, params: &Params) -> u32 {
+        params.part1.run(cave).0
+    }
+
+    fn part2(cave: &Cave, params: &Params) -> u32 {
+        match params.method {
+            Method::Flood => params.part2.flood(cave).unwrap_or_else(|| params.part2.run(cave)).0,
+            Method::Simulate => params.part2.run(cave).0,
+        }
+    }
+
+    fn variant(name: &str) -> Option<Params> {
+        match name {
+            "flood" => Some(Params { method: Method::Flood, ..Params::default() }),
+            "resume" => {
+                let mut params = Params::default();
+                params.part1.resume = true;
+                params.part2.resume = true;
+                Some(params)
+            }
+            _ => None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use proptest::test_runner::FileFailurePersistence;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day14/sample14.txt");
+
+    #[test_case(SAMPLE => 24 ; "sample")]
+    fn test_part1(input: &str) -> u32 {
+        Day14::part1(&Day14::parse(input).unwrap(), &Params::default())
+    }
+
+    #[test_case(SAMPLE => 93 ; "sample")]
+    fn test_part2(input: &str) -> u32 {
+        Day14::part2(&Day14::parse(input).unwrap(), &Params::default())
+    }
+
+    #[test_case("0,5 -> 2,5\n", 1 => (1, 45) ; "rocks at the left edge")]
+    #[test_case("5,1 -> 6,1\n", 0 => (0, 9) ; "source left of every rock")]
+    #[test_case("-3,2 -> -3,4 -> 3,4 -> 3,2\n", 0 => (14, 14) ; "bowl around negative x")]
+    fn test_any_layout(input: &str, source_x: i64) -> (u32, u32) {
+        let source = Point::new(source_x, 0);
+        let params = Params { part1: SandRules::new(source, None), part2: SandRules::new(source, Some(2)), method: Method::Simulate };
+        let cave = Day14::parse(input).unwrap();
+        (Day14::part1(&cave, &params), Day14::part2(&cave, &params))
+    }
+
+    #[test]
+    fn test_diagonal_path() {
+        let err = Cave::new(&[vec![Point::new(0, 0), Point::new(0, 2)], vec![Point::new(0, 0), Point::new(1, 2)]]).unwrap_err();
+        assert_eq!("rock paths must be horizontal or vertical, but line 2 goes from 0,0 to 1,2", err.to_string());
+    }
+
+    #[test]
+    fn test_empty_cave() {
+        let mut cave = Cave::new(&[]).unwrap();
+        assert_eq!((None, None), (cave.rock_bounds(), cave.bounds()));
+        assert_eq!(Grain::Fell, cave.drop_sand(Point::new(500, 0), None));
+        assert_eq!(0, Params::default().part2.run(&cave).0);
+        assert_eq!("", cave.to_string());
+        assert_eq!("+\n", cave.render(&Params::default().part2));
+    }
+
+    #[test]
+    fn test_cave() {
+        let mut cave = Day14::parse(SAMPLE).unwrap();
+        assert_eq!(Some((Point::new(494, 4), Point::new(503, 9))), cave.rock_bounds());
+        assert_eq!(Grain::Rested(Point::new(500, 8)), cave.drop_sand(Point::new(500, 0), None));
+        assert_eq!(Grain::Rested(Point::new(499, 8)), cave.drop_sand(Point::new(500, 0), None));
+        cave.simulate(&Params::default().part1);
+        assert_eq!(24, cave.sand());
+        assert_eq!(Some((Point::new(494, 2), Point::new(503, 9))), cave.bounds());
+        assert_eq!(Some(Tile::Sand), cave.get(Point::new(500, 2)));
+        assert_eq!(Some(Tile::Rock), cave.get(Point::new(494, 9)));
+        assert_eq!(Grain::Fell, cave.drop_sand(Point::new(500, 0), None));
+        assert_eq!(Grain::Rested(Point::new(500, 0)), cave.drop_sand(Point::new(500, 0), Some(1)));
+        assert_eq!(Grain::Blocked, cave.drop_sand(Point::new(500, 0), Some(1)));
+        assert!(cave.to_string().starts_with("......o...\n"));
+    }
+
+    #[test]
+    fn test_rules() {
+        let cave = Day14::parse(SAMPLE).unwrap();
+        let (rested, after) = Params::default().part2.run(&cave);
+        assert_eq!((93, 93), (rested, after.sand()));
+        assert_eq!(0, cave.sand());
+        assert_eq!(Some(Tile::Sand), after.get(Point::new(490, 10)));
+
+        // A shallower floor holds less sand.
+        assert_eq!(79, SandRules::new(Point::new(500, 0), Some(1)).run(&cave).0);
+
+        // The left source spills into the abyss straight away. Stopping there ends
+        // the run; otherwise the right source keeps pouring into the basin.
+        let mut rules = SandRules { sources: vec![Point::new(480, 0), Point::new(500, 0)], floor: None, stop_at_abyss: true, resume: false };
+        assert_eq!(0, rules.run(&cave).0);
+        rules.stop_at_abyss = false;
+        assert_eq!(24, rules.run(&cave).0);
+
+        // Two sources under one floor share the same pile.
+        let rules = SandRules { sources: vec![Point::new(500, 0), Point::new(500, 0)], floor: Some(2), stop_at_abyss: true, resume: false };
+        assert_eq!(93, rules.run(&cave).0);
+    }
+
+    #[test]
+    fn test_flood() {
+        let cave = Day14::parse(SAMPLE).unwrap();
+        let rules = Params::default().part2;
+        let ((count, flooded), (expected, simulated)) = (rules.flood(&cave).unwrap(), rules.run(&cave));
+        assert_eq!((expected, simulated.to_string()), (count, flooded.to_string()));
+        assert!(SandRules::new(Point::new(500, 0), None).flood(&cave).is_none());
+
+        let rules = SandRules { sources: vec![Point::new(500, 0), Point::new(497, 3), Point::new(520, 1)], floor: Some(3), stop_at_abyss: true, resume: false };
+        assert_eq!(rules.run(&cave).1.to_string(), rules.flood(&cave).unwrap().1.to_string());
+
+        assert_eq!("93", crate::runner::answer::<Day14>(SAMPLE, 2, Some("flood")).unwrap());
+        assert_eq!("no such variant: fast", crate::runner::answer::<Day14>(SAMPLE, 2, Some("fast")).unwrap_err().to_string());
+    }
+
+    fn resume_vs_restart(cave: &Cave, floor: Option<i64>) -> (u64, u64) {
+        let mut rules = SandRules::new(Point::new(500, 0), floor);
+        let (count, plain) = rules.run(cave);
+        rules.resume = true;
+        let (resumed_count, resumed) = rules.run(cave);
+        assert_eq!((count, plain.to_string()), (resumed_count, resumed.to_string()));
+        (resumed.steps(), plain.steps())
+    }
+
+    #[test]
+    fn test_resume() {
+        let cave = Day14::parse(SAMPLE).unwrap();
+        assert_eq!((34, 147), resume_vs_restart(&cave, None));
+        assert_eq!((92, 606), resume_vs_restart(&cave, Some(2)));
+
+        // A deep, wide bowl, so every grain has a long way to fall.
+        let bowl = Day14::parse("450,100 -> 450,200 -> 550,200 -> 550,100\n").unwrap();
+        let (resumed, plain) = resume_vs_restart(&bowl, None);
+        assert!(resumed * 100 < plain, "{resumed} vs {plain} steps");
+
+        assert_eq!("24", crate::runner::answer::<Day14>(SAMPLE, 1, Some("resume")).unwrap());
+    }
+
+    fn arb_paths() -> impl Strategy<Value = Vec<Vec<Point>>> {
+        let segment = (490..511_i64, 1..12_i64, any::<bool>(), 0..6_i64)
+            .prop_map(|(x, y, vertical, len)| vec![Point::new(x, y), if vertical { Point::new(x, y + len) } else { Point::new(x + len, y) }]);
+        prop::collection::vec(segment, 1..8)
+    }
+
+    proptest! {
+        #![proptest_config(ProptestConfig {
+            failure_persistence: Some(Box::new(FileFailurePersistence::WithSource("regressions"))),
+            ..ProptestConfig::default()
+        })]
+
+        #[test]
+        fn test_flood_matches_simulate(paths in arb_paths(), floor in 1..4_i64, x in 495..506_i64) {
+            let cave = Cave::new(&paths).unwrap();
+            let rules = SandRules::new(Point::new(x, 0), Some(floor));
+            let (count, flooded) = rules.flood(&cave).unwrap();
+            let (expected, simulated) = rules.run(&cave);
+            prop_assert_eq!(expected, count);
+            prop_assert_eq!(simulated.to_string(), flooded.to_string());
+        }
+
+        #[test]
+        fn test_resume_matches_restart(paths in arb_paths(), floor in prop::option::of(1..4_i64), xs in prop::collection::vec(495..506_i64, 1..3), stop_at_abyss: bool) {
+            let cave = Cave::new(&paths).unwrap();
+            let mut rules = SandRules { sources: xs.into_iter().map(|x| Point::new(x, 0)).collect(), floor, stop_at_abyss, resume: false };
+            let (count, plain) = rules.run(&cave);
+            rules.resume = true;
+            let (resumed_count, resumed) = rules.run(&cave);
+            prop_assert_eq!(count, resumed_count);
+            prop_assert_eq!(plain.to_string(), resumed.to_string());
+            prop_assert!(resumed.steps() <= plain.steps());
+        }
+    }
+
+    #[test]
+    fn test_render() {
+        let mut cave = Day14::parse(SAMPLE).unwrap();
+        let rules = Params::default().part1;
+        assert_eq!(concat!(
+            "......+...\n", "..........\n", "..........\n", "..........\n", "....#...##\n",
+            "....#...#.\n", "..###...#.\n", "........#.\n", "........#.\n", "#########.\n",
+        ), cave.render(&rules));
+
+        let mut frames = Vec::new();
+        assert_eq!(24, cave.animate(&rules, 5, |cave, rested| frames.push((rested, cave.render(&rules)))));
+        assert_eq!(vec![5, 10, 15, 20, 24], frames.iter().map(|(rested, _)| *rested).collect::<Vec<_>>());
+        assert_eq!(concat!(
+            "......+...\n", "..........\n", "......o...\n", ".....ooo..\n", "....#ooo##\n",
+            "...o#ooo#.\n", "..###ooo#.\n", "....oooo#.\n", ".o.ooooo#.\n", "#########.\n",
+        ), frames[4].1);
+
+        let floored = Params::default().part2.run(&Day14::parse(SAMPLE).unwrap()).1;
+        let rows: Vec<String> = floored.render(&Params::default().part2).lines().map(String::from).collect();
+        assert_eq!((12, 21), (rows.len(), rows[0].len()));
+        assert_eq!("..........o..........", rows[0]);
+        assert_eq!("#".repeat(21), rows[11]);
+    }
+
+    #[test]
+    fn test_images() {
+        let rules = Params::default().part1;
+        let mut cave = Day14::parse(SAMPLE).unwrap();
+        cave.simulate(&rules);
+
+        let mut ppm = Vec::new();
+        cave.write_ppm(&rules, 2, &mut ppm).unwrap();
+        let header = b"P6\n20 20\n255\n";
+        assert_eq!(header, &ppm[..header.len()]);
+        assert_eq!(header.len() + 20 * 20 * 3, ppm.len());
+        // The source is drawn 2x2 at column 6 of the top row.
+        assert_eq!(SOURCE, ppm[header.len() + 12 * 3..][..3]);
+        assert_eq!(SOURCE, ppm[header.len() + (20 + 13) * 3..][..3]);
+
+        let mut png = Vec::new();
+        cave.write_png(&rules, 2, &mut png).unwrap();
+        let mut reader = png::Decoder::new(png.as_slice()).read_info().unwrap();
+        let mut pixels = vec![0; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut pixels).unwrap();
+        assert_eq!((20, 20, png::ColorType::Rgb), (info.width, info.height, info.color_type));
+        assert_eq!(&ppm[header.len()..], &pixels[..]);
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day14::parse("498,4 -> 498,6\n503,4 -> 502,x\n").unwrap_err();
+        assert_eq!("invalid coordinate at line 2, column 14: `x`", err.to_string());
+        let err = Day14::parse("498,4 -> 497,6\n").unwrap_err();
+        assert_eq!("rock paths must be horizontal or vertical, but line 1 goes from 498,4 to 497,6", err.to_string());
+    }
+}
@@ -0,0 +1,163 @@
+use nom::{
+    IResult,
+    bytes::complete::tag,
+    sequence::tuple,
+    combinator::map,
+};
+use std::collections::HashMap;
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{geom::Point, parse::{signed, Location}, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day15/input15.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Report(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Report(loc) => write!(f, "expected `Sensor at x=X, y=Y: closest beacon is at x=X, y=Y` at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+pub struct Map {
+    sensors: Vec<(i64, i64, i64)>,
+    zones: HashMap<i64, Vec<(i64, i64)>>,
+}
+
+impl Map {
+    fn new(sensors: Vec<(i64, i64, i64)>) -> Self {
+        Self { sensors, zones: HashMap::new() }
+    }
+    fn find_zones(&mut self, st: i64, end: i64) {
+        (st..=end).for_each(|row| {
+            let mut cvrd = Vec::new();
+            for &(x, y, d) in self.sensors.iter() {
+                if y >= row - d && y <= row + d {
+                    let delta = d - (y - row).abs();
+                    cvrd.push((x - delta, x + delta));
+                }
+            }
+            cvrd.sort_unstable_by_key(|a| a.0);
+            let merged = merge(cvrd);
+            self.zones.insert(row, merged);
+        })
+    }
+    fn find_exluded(&self, row: i64) -> u64 {
+        self.zones[&row].iter().map(|&(st, end)| st.abs_diff(end)).sum()
+    }
+    fn find_beacon(&self, lmt: i64) -> (i64, i64) {
+        for y in 0..=lmt {
+            if let Some(ranges) = self.zones.get(&y) {
+                let mut x = 0;
+                for &(st, end) in ranges {
+                    if x < st { return (y, x); }
+                    x = end.max(x) + 1;
+                    if x > lmt { break; }
+                }
+                if x <= lmt { return (y, x); }
+            }
+        }
+        (0, 0)
+    }
+}
+
+fn merge(cvrd: Vec<(i64, i64)>) -> Vec<(i64, i64)> {
+    let mut merged: Vec<(i64, i64)> = Vec::new();
+    for zone in cvrd {
+        if let Some(last) = merged.last_mut() {
+            if last.1 >= zone.0 {
+                last.1 = last.1.max(zone.1);
+                continue;
+            }
+        }
+        merged.push(zone);
+    }
+    merged
+}
+
+fn line_parser(input: &str) -> IResult<&str, (Point, Point)> {
+    map(tuple((tag("Sensor at x="), signed, tag(", y="), signed, tag(": closest beacon is at x="), signed, tag(", y="), signed)),|(_, sx, _, sy, _, bx, _, by)| {
+            (Point::new(sx, sy), Point::new(bx, by))
+        },
+    )(input)
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct Params {
+    pub row: i64,
+    pub limit: i64,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self { row: 2000000, limit: 4000000 }
+    }
+}
+
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Parsed<'a> = Map;
+    type Params = Params;
+    type Answer1 = u64;
+    type Answer2 = i64;
+
+    fn parse(input: &str) -> Result<Map> {
+        let sensors: Vec<(i64, i64, i64)> = input.lines().map(|l| {
+            let (sensor, beacon) = match line_parser(l) {
+                Ok(("", report)) => report,
+                Ok((rest, _)) => return Err(ParseError::Report(Location::of(input, rest))),
+                Err(e) => return Err(ParseError::Report(Location::of(input, crate::parse::unparsed(&e, l)))),
+            };
+            Ok((sensor.x, sensor.y, sensor.manhattan(beacon) as i64))
+        }).collect::<Result<_, _>>()?;
+        Ok(Map::new(sensors))
+    }
+
+    fn part1(map: &Map, params: &Params) -> u64 {
+        let mut map = map.clone();
+        map.find_zones(params.row, params.row);
+        map.find_exluded(params.row)
+    }
+
+    fn part2(map: &Map, params: &Params) -> i64 {
+        let mut map = map.clone();
+        map.find_zones(0, params.limit);
+        let (y, x) = map.find_beacon(params.limit);
+        x * 4000000 + y
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day15/sample15.txt");
+    const SAMPLE_PARAMS: Params = Params { row: 10, limit: 20 };
+
+    #[test_case(SAMPLE => 26 ; "sample")]
+    fn test_part1(input: &str) -> u64 {
+        Day15::part1(&Day15::parse(input).unwrap(), &SAMPLE_PARAMS)
+    }
+
+    #[test_case(SAMPLE => 56000011 ; "sample")]
+    fn test_part2(input: &str) -> i64 {
+        Day15::part2(&Day15::parse(input).unwrap(), &SAMPLE_PARAMS)
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day15::parse("Sensor at x=2, y=18: closest beacon is at x=-2, y=15\nSensor at x=9, y=16: closest beacon at x=10, y=16\n").unwrap_err();
+        assert_eq!("expected `Sensor at x=X, y=Y: closest beacon is at x=X, y=Y` at line 2, column 20: `: closest beacon at x=10, y=16`", err.to_string());
+    }
+}
@@ -0,0 +1,668 @@
+use color_eyre::eyre::{bail, Result};
+use crate::{parse::{unsigned, Location}, runner::Solution, search::DistanceMatrix};
+use itertools::Itertools;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use nom::{
+    IResult,
+    bytes::complete::{tag, take_while1},
+    sequence::tuple,
+    character::complete::alpha1,
+    combinator::map,
+    multi::separated_list1,
+};
+
+pub const INPUT: &str = include_str!("../../bin/day16/input16.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Scan(Location),
+    UnknownValve(Location),
+    TooManyValves,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Scan(loc) => write!(f, "expected `Valve XX has flow rate=N; tunnels lead to valves YY, ZZ` at {loc}"),
+            ParseError::UnknownValve(loc) => write!(f, "tunnel leads to an unknown valve at {loc}"),
+            ParseError::TooManyValves => write!(f, "more than {MAX_USEFUL} valves with non-zero flow"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn until_valve_name(input: &str) -> IResult<&str, &str> {
+    take_while1(|c: char| !c.is_uppercase())(input)
+}
+
+fn parse_list(s: &str) -> IResult<&str, Vec<&str>> {
+    separated_list1(tag(", "), alpha1)(s)
+}
+
+fn parse_line(input: &str) -> IResult<&str, (&str, u64, HashSet<&str>)> {
+    map(tuple((tag("Valve "), alpha1, tag(" has flow rate="), unsigned, until_valve_name, parse_list)),|(_, name, _, flow_rate, _, adj_tunnels)| {
+            (name, flow_rate, adj_tunnels.into_iter().collect())
+        },
+    )(input)
+}
+
+#[derive(Debug, Clone)]
+struct Valve<'a> {
+    flow: u64,
+    nghs: HashSet<&'a str>,
+}
+
+impl<'a> Valve<'a> {
+    fn new(flow: u64, nghs: HashSet<&'a str>) -> Self {
+        Self { flow, nghs }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Graph<'a> {
+    valves: HashMap<&'a str, Valve<'a>>,
+    /// Every valve by name, indexing `all_dist`.
+    ids: Vec<&'a str>,
+    all_dist: DistanceMatrix,
+}
+
+/// Valves with flow are tracked as bits, and agents split them using tables of
+/// `2^n` entries, so `n` has to stay small.
+const MAX_USEFUL: usize = 20;
+
+type Memo = HashMap<(usize, u64, u64), u64>;
+
+/// The valves one agent opens, with the minute each one is opened in.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Route<'a> {
+    pub opens: Vec<(u64, &'a str)>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Action {
+    Move,
+    Open,
+}
+
+/// What one agent does during one minute.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Step<'a> {
+    pub minute: u64,
+    pub agent: usize,
+    pub action: Action,
+    pub valve: &'a str,
+}
+
+/// The best way for a team of agents to release pressure, both as the valves
+/// each agent opens and as the full list of steps ordered by minute and agent.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Plan<'a> {
+    pub start: &'a str,
+    pub minutes: u64,
+    pub pressure: u64,
+    pub routes: Vec<Route<'a>>,
+    pub steps: Vec<Step<'a>>,
+}
+
+/// Options for [`Graph::to_dot`].
+#[derive(Debug, Clone, Copy, Default)]
+pub struct DotOptions<'p, 'a> {
+    /// Draw only the start and the flowing valves, joined by walking distances.
+    pub compressed: bool,
+    /// Colour the tunnels walked and valves opened by each agent of this plan.
+    pub plan: Option<&'p Plan<'a>>,
+}
+
+const AGENT_COLORS: [&str; 5] = ["red", "blue", "darkgreen", "orange", "purple"];
+
+impl <'a>Graph<'a> {
+    fn new() -> Self {
+        Self {
+            valves: HashMap::new(),
+            ids: Vec::new(),
+            all_dist: DistanceMatrix::floyd_warshall(0, []),
+        }
+    }
+    fn find_dist(&mut self) {
+        self.ids = self.valves.keys().copied().collect();
+        self.ids.sort_unstable();
+        let index: HashMap<&str, usize> = self.ids.iter().enumerate().map(|(i, &n)| (n, i)).collect();
+        let edges = self.ids.iter().flat_map(|n| self.valves[n].nghs.iter().map(|ngh| (index[n], index[ngh], 1)));
+        self.all_dist = DistanceMatrix::floyd_warshall(self.ids.len(), edges);
+    }
+    /// Minutes to walk from one valve to another, or `None` if no tunnels connect them.
+    pub fn distance(&self, from: &str, to: &str) -> Option<u64> {
+        let index = |name| self.ids.binary_search(&name).ok();
+        self.all_dist.get(index(from)?, index(to)?)
+    }
+    /// Valves with non-zero flow that cannot be reached from `start`, and so are
+    /// left out of planning.
+    pub fn unreachable(&self, start: &str) -> Vec<&'a str> {
+        self.ids.iter().copied().filter(|n| self.valves[n].flow > 0 && self.distance(start, n).is_none()).collect()
+    }
+    /// The most pressure `agents` agents starting together at `start` can release
+    /// within `minutes`, and the route each of them takes.
+    pub fn plan(&self, start: &str, minutes: u64, agents: usize) -> Result<Plan<'a>> {
+        let start = self.check_plan(start, agents)?;
+        let planner = Planner::new(self, start);
+        let masks = planner.split(minutes, agents);
+        let mut memo = HashMap::new();
+        let routes: Vec<Route> = masks.iter().map(|&mask| planner.route(minutes, mask, &mut memo)).collect();
+        let pressure = routes.iter().flat_map(|route| &route.opens).map(|&(minute, valve)| self.valves[valve].flow * (minutes - minute)).sum();
+        let steps = self.steps(start, &routes);
+        Ok(Plan { start, minutes, pressure, routes, steps })
+    }
+    /// Checks that `plan` can start from `start` with `agents` agents, returning
+    /// the valve's name as stored in the graph.
+    pub fn check_plan(&self, start: &str, agents: usize) -> Result<&'a str> {
+        let Some(&start) = self.ids.iter().find(|&&n| n == start) else {
+            bail!("no such valve: {start}");
+        };
+        if agents == 0 {
+            bail!("planning needs at least one agent");
+        }
+        Ok(start)
+    }
+    /// The neighbour of `from` that starts a shortest walk to `to`, picking the
+    /// first by name when there are several.
+    fn next_hop(&self, from: &'a str, to: &str) -> &'a str {
+        let left = self.distance(from, to).expect("routes only visit reachable valves");
+        self.valves[from].nghs.iter().copied()
+            .filter(|n| self.distance(n, to) == Some(left - 1))
+            .min()
+            .expect("a shortest path continues through some neighbour")
+    }
+    /// Expands each route into the moves and opens that carry it out.
+    fn steps(&self, start: &'a str, routes: &[Route<'a>]) -> Vec<Step<'a>> {
+        let mut steps = Vec::new();
+        for (agent, route) in routes.iter().enumerate() {
+            let (mut pos, mut minute) = (start, 1);
+            for &(_, valve) in &route.opens {
+                while pos != valve {
+                    pos = self.next_hop(pos, valve);
+                    steps.push(Step { minute, agent, action: Action::Move, valve: pos });
+                    minute += 1;
+                }
+                steps.push(Step { minute, agent, action: Action::Open, valve });
+                minute += 1;
+            }
+        }
+        steps.sort_by_key(|step| (step.minute, step.agent));
+        steps
+    }
+    /// Renders the valves and tunnels in Graphviz DOT, circling `start`. Valves
+    /// without flow are drawn as bare points, or left out entirely in the
+    /// compressed view.
+    pub fn to_dot(&self, start: &str, options: DotOptions<'_, 'a>) -> String {
+        let mut opened = HashMap::new();
+        let mut walked = HashMap::new();
+        if let Some(plan) = options.plan {
+            for (agent, route) in plan.routes.iter().enumerate() {
+                route.opens.iter().for_each(|&(_, valve)| { opened.insert(valve, agent); });
+                if options.compressed {
+                    let stops = std::iter::once(plan.start).chain(route.opens.iter().map(|&(_, valve)| valve));
+                    stops.tuple_windows().for_each(|(from, to)| { walked.insert((from, to), agent); });
+                }
+            }
+            if !options.compressed {
+                let mut pos = vec![plan.start; plan.routes.len()];
+                for step in plan.steps.iter().filter(|step| step.action == Action::Move) {
+                    walked.insert((pos[step.agent], step.valve), step.agent);
+                    pos[step.agent] = step.valve;
+                }
+            }
+        }
+        let shown: Vec<&str> = self.ids.iter().copied()
+            .filter(|n| !options.compressed || self.valves[n].flow > 0 || *n == start)
+            .collect();
+
+        let mut lines = vec!["digraph valves {".to_string(), "    node [shape=circle];".to_string()];
+        for &name in &shown {
+            let flow = self.valves[name].flow;
+            let mut attrs = if flow > 0 || name == start {
+                vec![format!("label=\"{name}\\n{flow}\"")]
+            } else {
+                vec!["shape=point".to_string(), format!("xlabel=\"{name}\"")]
+            };
+            if name == start {
+                attrs.push("peripheries=2".to_string());
+            }
+            if let Some(&agent) = opened.get(name) {
+                attrs.push(format!("style=filled, fillcolor={}", AGENT_COLORS[agent % AGENT_COLORS.len()]));
+            }
+            lines.push(format!("    {name} [{}];", attrs.join(", ")));
+        }
+        for (i, &from) in shown.iter().enumerate() {
+            for &to in &shown[i + 1..] {
+                let (there, back) = if options.compressed {
+                    (self.distance(from, to), self.distance(to, from))
+                } else {
+                    let tunnel = |a: &str, b| self.valves[a].nghs.contains(b).then_some(1);
+                    (tunnel(from, to), tunnel(to, from))
+                };
+                let edge = |a: &str, b: &str, mutual: bool, d: u64, agent: Option<&usize>| {
+                    let mut attrs = Vec::new();
+                    if mutual {
+                        attrs.push("dir=none".to_string());
+                    }
+                    if options.compressed {
+                        attrs.push(format!("label={d}"));
+                    }
+                    if let Some(agent) = agent {
+                        attrs.push(format!("color={}, penwidth=2", AGENT_COLORS[agent % AGENT_COLORS.len()]));
+                    }
+                    match attrs.is_empty() {
+                        true => format!("    {a} -> {b};"),
+                        false => format!("    {a} -> {b} [{}];", attrs.join(", ")),
+                    }
+                };
+                match (there, back) {
+                    (Some(d), Some(e)) if d == e => {
+                        lines.push(edge(from, to, true, d, walked.get(&(from, to)).or(walked.get(&(to, from)))));
+                    }
+                    _ => {
+                        lines.extend(there.map(|d| edge(from, to, false, d, walked.get(&(from, to)))));
+                        lines.extend(back.map(|e| edge(to, from, false, e, walked.get(&(to, from)))));
+                    }
+                }
+            }
+        }
+        lines.push("}".to_string());
+        lines.join("\n") + "\n"
+    }
+    /// Re-simulates `plan` minute by minute, checking every step against the
+    /// tunnels, and returns the pressure released. The puzzle-style narrative
+    /// is written to `out`.
+    pub fn replay(&self, plan: &Plan<'a>, out: &mut impl fmt::Write) -> Result<u64> {
+        let agents = plan.routes.len();
+        let mut pos = vec![plan.start; agents];
+        let mut open = BTreeSet::new();
+        let mut released = 0;
+        let mut steps = plan.steps.iter().peekable();
+        for minute in 1..=plan.minutes {
+            if minute > 1 {
+                writeln!(out)?;
+            }
+            writeln!(out, "== Minute {minute} ==")?;
+            let rate: u64 = open.iter().map(|v| self.valves[v].flow).sum();
+            released += rate;
+            match open.iter().collect::<Vec<_>>()[..] {
+                [] => writeln!(out, "No valves are open.")?,
+                [valve] => writeln!(out, "Valve {valve} is open, releasing {rate} pressure.")?,
+                [a, b] => writeln!(out, "Valves {a} and {b} are open, releasing {rate} pressure.")?,
+                [ref rest @ .., last] => writeln!(out, "Valves {}, and {last} are open, releasing {rate} pressure.", rest.iter().join(", "))?,
+            }
+            let mut acted = vec![false; agents];
+            while let Some(&Step { agent, action, valve, .. }) = steps.next_if(|step| step.minute == minute) {
+                if agent >= agents || std::mem::replace(&mut acted[agent], true) {
+                    bail!("minute {minute}: agent {agent} cannot act");
+                }
+                let (who, s) = match (agent, agents) {
+                    (0, _) => ("You".to_string(), ""),
+                    (_, 2) => ("The elephant".to_string(), "s"),
+                    _ => (format!("Elephant {agent}"), "s"),
+                };
+                match action {
+                    Action::Move if self.valves[pos[agent]].nghs.contains(valve) => {
+                        pos[agent] = valve;
+                        writeln!(out, "{who} move{s} to valve {valve}.")?;
+                    }
+                    Action::Move => bail!("minute {minute}: no tunnel from {} to {valve}", pos[agent]),
+                    Action::Open if pos[agent] != valve => bail!("minute {minute}: agent {agent} is not at valve {valve}"),
+                    Action::Open if !open.insert(valve) => bail!("minute {minute}: valve {valve} is already open"),
+                    Action::Open => writeln!(out, "{who} open{s} valve {valve}.")?,
+                }
+            }
+        }
+        if let Some(step) = steps.next() {
+            bail!("step at minute {} is out of order or past the time budget", step.minute);
+        }
+        Ok(released)
+    }
+}
+
+/// The valves worth opening as seen from one start valve, interned so that a set
+/// of them fits in a `u64` bitmask. The start valve is interned last.
+struct Planner<'a> {
+    names: Vec<&'a str>,
+    flows: Vec<u64>,
+    /// Minutes to walk between any two interned valves.
+    dist: Vec<Vec<u64>>,
+}
+
+impl<'a> Planner<'a> {
+    fn new(graph: &Graph<'a>, start: &'a str) -> Self {
+        let mut names: Vec<&str> = graph.ids.iter().copied()
+            .filter(|n| graph.valves[n].flow > 0 && graph.distance(start, n).is_some())
+            .collect();
+        names.push(start);
+        // Tunnels may be one-way, so reachable valves can still be cut off from
+        // each other; such moves get a distance no time budget covers.
+        let dist = names.iter().map(|from| {
+            names.iter().map(|to| graph.distance(from, to).unwrap_or(u64::MAX / 2)).collect()
+        }).collect();
+        let flows = names.iter().map(|n| graph.valves[n].flow).collect();
+        Self { names, flows, dist }
+    }
+    /// Number of interned valves worth opening; the start valve comes right after.
+    fn useful(&self) -> usize {
+        self.names.len() - 1
+    }
+    /// Valves not in `opened` that can be reached and opened from `pos` with time
+    /// to spare, with the minutes left once each is open.
+    fn moves(&self, pos: usize, time_left: u64, opened: u64) -> impl Iterator<Item = (usize, u64)> + '_ {
+        (0..self.useful()).filter(move |&j| opened & 1 << j == 0).filter_map(move |j| {
+            time_left.checked_sub(self.dist[pos][j] + 1).filter(|&t| t > 0).map(|t| (j, t))
+        })
+    }
+    /// Most pressure still releasable from `pos` with `time_left` minutes, given the
+    /// valves already `opened`.
+    fn max_relief(&self, pos: usize, time_left: u64, opened: u64, memo: &mut Memo) -> u64 {
+        if let Some(&relief) = memo.get(&(pos, time_left, opened)) {
+            return relief;
+        }
+        let relief = self.moves(pos, time_left, opened)
+            .map(|(j, t)| self.flows[j] * t + self.max_relief(j, t, opened | 1 << j, memo))
+            .max()
+            .unwrap_or(0);
+        memo.insert((pos, time_left, opened), relief);
+        relief
+    }
+    /// Records in `best[mask]` the most pressure released by a single agent that
+    /// opens exactly the valves in `mask`.
+    fn relief_by_set(&self, pos: usize, time_left: u64, opened: u64, relieved: u64, best: &mut [u64]) {
+        best[opened as usize] = best[opened as usize].max(relieved);
+        for (j, t) in self.moves(pos, time_left, opened) {
+            self.relief_by_set(j, t, opened | 1 << j, relieved + self.flows[j] * t, best);
+        }
+    }
+    /// Splits the valves between `agents` agents, returning the set each one may open.
+    fn split(&self, minutes: u64, agents: usize) -> Vec<u64> {
+        let n = self.useful();
+        let full = (1 << n) - 1;
+        if agents == 1 {
+            return vec![full as u64];
+        }
+        let mut best = vec![0; 1 << n];
+        self.relief_by_set(n, minutes, 0, 0, &mut best);
+        // Subset maximum: afterwards `best[mask]` covers every subset of `mask`.
+        for bit in 0..n {
+            for mask in 0..best.len() {
+                if mask & 1 << bit != 0 {
+                    best[mask] = best[mask].max(best[mask ^ 1 << bit]);
+                }
+            }
+        }
+        // After round `k`, `team[mask]` is the best total for `k + 1` agents opening
+        // only valves in `mask`, and `choice[k - 1][mask]` the newest agent's share.
+        let mut team = best.clone();
+        let mut choice = Vec::new();
+        for k in 1..agents {
+            let masks: Vec<usize> = if k + 1 == agents { vec![full] } else { (0..=full).collect() };
+            let mut next = vec![0; 1 << n];
+            let mut chosen = vec![0; 1 << n];
+            for mask in masks {
+                // Walk every submask of `mask`, including the empty one.
+                let mut sub = mask;
+                loop {
+                    let total = best[sub] + team[mask ^ sub];
+                    if total >= next[mask] {
+                        (next[mask], chosen[mask]) = (total, sub);
+                    }
+                    if sub == 0 { break }
+                    sub = (sub - 1) & mask;
+                }
+            }
+            team = next;
+            choice.push(chosen);
+        }
+        let mut masks = Vec::new();
+        let mut rest = full;
+        for chosen in choice.iter().rev() {
+            masks.push(chosen[rest] as u64);
+            rest ^= chosen[rest];
+        }
+        masks.push(rest as u64);
+        masks
+    }
+    /// The best route for one agent allowed to open only the valves in `allowed`.
+    fn route(&self, minutes: u64, allowed: u64, memo: &mut Memo) -> Route<'a> {
+        let mut opened = !allowed;
+        let (mut pos, mut time_left) = (self.useful(), minutes);
+        let mut opens = Vec::new();
+        let mut left = self.max_relief(pos, time_left, opened, memo);
+        while left > 0 {
+            let (j, t) = self.moves(pos, time_left, opened)
+                .find(|&(j, t)| self.flows[j] * t + self.max_relief(j, t, opened | 1 << j, memo) == left)
+                .expect("the memoized optimum is reachable by some move");
+            opens.push((minutes - t, self.names[j]));
+            left -= self.flows[j] * t;
+            (pos, time_left, opened) = (j, t, opened | 1 << j);
+        }
+        Route { opens }
+    }
+}
+
+/// How long and with how many agents a part plans.
+#[derive(Debug, Clone, Copy)]
+pub struct Budget {
+    pub minutes: u64,
+    pub agents: usize,
+}
+
+#[derive(Debug, Clone)]
+pub struct Params {
+    pub start: String,
+    pub part1: Budget,
+    pub part2: Budget,
+}
+
+impl Default for Params {
+    fn default() -> Self {
+        Self {
+            start: "AA".to_string(),
+            part1: Budget { minutes: 30, agents: 1 },
+            part2: Budget { minutes: 26, agents: 2 },
+        }
+    }
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Parsed<'a> = Graph<'a>;
+    type Params = Params;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Graph<'_>> {
+        let mut graph = Graph::new();
+        for line in input.lines() {
+            let (name, flow, nghs) = match parse_line(line) {
+                Ok(("", scan)) => scan,
+                Ok((rest, _)) => return Err(ParseError::Scan(Location::of(input, rest)).into()),
+                Err(e) => return Err(ParseError::Scan(Location::of(input, crate::parse::unparsed(&e, line))).into()),
+            };
+            graph.valves.insert(name, Valve::new(flow, nghs));
+        }
+        if let Some(&ngh) = graph.valves.values().flat_map(|v| &v.nghs).find(|n| !graph.valves.contains_key(*n)) {
+            return Err(ParseError::UnknownValve(Location::of(input, ngh)).into());
+        }
+        graph.find_dist();
+        if graph.valves.values().filter(|v| v.flow > 0).count() > MAX_USEFUL {
+            return Err(ParseError::TooManyValves.into());
+        }
+        Ok(graph)
+    }
+
+    fn check(graph: &Graph<'_>, params: &Params) -> Result<()> {
+        graph.check_plan(&params.start, params.part1.agents)?;
+        graph.check_plan(&params.start, params.part2.agents)?;
+        Ok(())
+    }
+
+    /// 0 if no plan can be made; `check` reports why.
+    fn part1(graph: &Graph<'_>, params: &Params) -> u64 {
+        let Budget { minutes, agents } = params.part1;
+        graph.plan(&params.start, minutes, agents).map_or(0, |plan| plan.pressure)
+    }
+
+    fn part2(graph: &Graph<'_>, params: &Params) -> u64 {
+        let Budget { minutes, agents } = params.part2;
+        graph.plan(&params.start, minutes, agents).map_or(0, |plan| plan.pressure)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::runner::solve;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day16/sample16.txt");
+
+    #[test_case(SAMPLE => 1651 ; "sample")]
+    fn test_part1(input: &str) -> u64 {
+        Day16::part1(&Day16::parse(input).unwrap(), &Params::default())
+    }
+
+    #[test_case(SAMPLE => 1707 ; "sample")]
+    fn test_part2(input: &str) -> u64 {
+        Day16::part2(&Day16::parse(input).unwrap(), &Params::default())
+    }
+
+    #[test]
+    fn test_plan() {
+        let graph = Day16::parse(SAMPLE).unwrap();
+        let plan = graph.plan("AA", 30, 1).unwrap();
+        assert_eq!(1651, plan.pressure);
+        let route = vec![(2, "DD"), (5, "BB"), (9, "JJ"), (17, "HH"), (21, "EE"), (24, "CC")];
+        assert_eq!(vec![Route { opens: route }], plan.routes);
+        assert_eq!(Step { minute: 1, agent: 0, action: Action::Move, valve: "DD" }, plan.steps[0]);
+        assert_eq!(Step { minute: 2, agent: 0, action: Action::Open, valve: "DD" }, plan.steps[1]);
+
+        let mut narrative = String::new();
+        assert_eq!(1651, graph.replay(&plan, &mut narrative).unwrap());
+        let expected = "\
+== Minute 1 ==
+No valves are open.
+You move to valve DD.
+
+== Minute 2 ==
+No valves are open.
+You open valve DD.
+
+== Minute 3 ==
+Valve DD is open, releasing 20 pressure.
+You move to valve AA.
+";
+        assert!(narrative.starts_with(expected), "{narrative}");
+        assert!(narrative.contains("== Minute 6 ==\nValves BB and DD are open, releasing 33 pressure.\n"), "{narrative}");
+        assert!(narrative.ends_with("== Minute 30 ==\nValves BB, CC, DD, EE, HH, and JJ are open, releasing 81 pressure.\n"), "{narrative}");
+    }
+
+    #[test]
+    fn test_replay_errors() {
+        let graph = Day16::parse(SAMPLE).unwrap();
+        let plan = graph.plan("AA", 30, 1).unwrap();
+        let mut teleport = plan.clone();
+        teleport.steps[0].valve = "JJ";
+        assert_eq!("minute 1: no tunnel from AA to JJ", graph.replay(&teleport, &mut String::new()).unwrap_err().to_string());
+        let mut reopen = plan.clone();
+        reopen.steps[4] = Step { minute: 5, agent: 0, action: Action::Open, valve: "DD" };
+        assert_eq!("minute 5: agent 0 is not at valve DD", graph.replay(&reopen, &mut String::new()).unwrap_err().to_string());
+        let mut late = plan.clone();
+        late.minutes = 20;
+        assert_eq!("step at minute 21 is out of order or past the time budget", graph.replay(&late, &mut String::new()).unwrap_err().to_string());
+    }
+
+    #[test_case("AA", 26, 2 => 1707 ; "you and an elephant")]
+    #[test_case("AA", 26, 3 => 1794 ; "three agents")]
+    #[test_case("AA", 10, 3 => 498 ; "three agents in a hurry")]
+    #[test_case("JJ", 30, 1 => 1807 ; "starting elsewhere")]
+    fn test_agents(start: &str, minutes: u64, agents: usize) -> u64 {
+        let graph = Day16::parse(SAMPLE).unwrap();
+        let plan = graph.plan(start, minutes, agents).unwrap();
+        assert_eq!(agents, plan.routes.len());
+        let opened: Vec<&str> = plan.routes.iter().flat_map(|route| route.opens.iter().map(|&(_, valve)| valve)).collect();
+        assert!(opened.iter().all_unique(), "agents open the same valve twice: {opened:?}");
+        assert_eq!(plan.pressure, graph.replay(&plan, &mut String::new()).unwrap());
+        plan.pressure
+    }
+
+    #[test]
+    fn test_plan_errors() {
+        let graph = Day16::parse(SAMPLE).unwrap();
+        assert_eq!("no such valve: XX", graph.plan("XX", 30, 1).unwrap_err().to_string());
+        assert_eq!("planning needs at least one agent", graph.plan("AA", 30, 0).unwrap_err().to_string());
+
+        // The start comes from the params, so an input without AA is fine.
+        let renamed = SAMPLE.replace("AA", "QQ");
+        let params = Params { start: "QQ".to_string(), ..Params::default() };
+        assert_eq!(("1651".to_string(), "1707".to_string()), solve::<Day16>(&renamed, &params).unwrap());
+        assert_eq!("no such valve: AA", solve::<Day16>(&renamed, &Params::default()).unwrap_err().to_string());
+        assert_eq!("no such valve: AA", crate::runner::answer::<Day16>(&renamed, 1, None).unwrap_err().to_string());
+        assert!(Day16::parse(&renamed).unwrap().to_dot("QQ", DotOptions::default()).contains("    QQ [label=\"QQ\\n0\", peripheries=2];\n"));
+    }
+
+    const LINE: &str = "\
+Valve AA has flow rate=0; tunnel leads to valve BB
+Valve BB has flow rate=5; tunnels lead to valves AA, CC
+Valve CC has flow rate=0; tunnels lead to valves BB, DD
+Valve DD has flow rate=3; tunnel leads to valve CC
+";
+
+    #[test]
+    fn test_dot() {
+        let graph = Day16::parse(LINE).unwrap();
+        assert_eq!("\
+digraph valves {
+    node [shape=circle];
+    AA [label=\"AA\\n0\", peripheries=2];
+    BB [label=\"BB\\n5\"];
+    CC [shape=point, xlabel=\"CC\"];
+    DD [label=\"DD\\n3\"];
+    AA -> BB [dir=none];
+    BB -> CC [dir=none];
+    CC -> DD [dir=none];
+}
+", graph.to_dot("AA", DotOptions::default()));
+
+        let plan = graph.plan("AA", 30, 1).unwrap();
+        assert_eq!("\
+digraph valves {
+    node [shape=circle];
+    AA [label=\"AA\\n0\", peripheries=2];
+    BB [label=\"BB\\n5\", style=filled, fillcolor=red];
+    DD [label=\"DD\\n3\", style=filled, fillcolor=red];
+    AA -> BB [dir=none, label=1, color=red, penwidth=2];
+    AA -> DD [dir=none, label=3];
+    BB -> DD [dir=none, label=2, color=red, penwidth=2];
+}
+", graph.to_dot("AA", DotOptions { compressed: true, plan: Some(&plan) }));
+        assert!(graph.to_dot("AA", DotOptions { compressed: false, plan: Some(&plan) }).contains("    CC -> DD [dir=none, color=red, penwidth=2];\n"));
+    }
+
+    #[test]
+    fn test_unreachable_valves() {
+        let input = format!("{SAMPLE}Valve YY has flow rate=0; tunnel leads to valve ZZ\nValve ZZ has flow rate=50; tunnel leads to valve YY\n");
+        let graph = Day16::parse(&input).unwrap();
+        assert_eq!(vec!["ZZ"], graph.unreachable("AA"));
+        assert_eq!(vec!["BB", "CC", "DD", "EE", "HH", "JJ"], graph.unreachable("YY"));
+        assert_eq!(None, graph.distance("AA", "ZZ"));
+        assert_eq!(Some(1), graph.distance("YY", "ZZ"));
+        assert_eq!(Some(5), graph.distance("AA", "HH"));
+        assert_eq!((1651, 1707), (Day16::part1(&graph, &Params::default()), Day16::part2(&graph, &Params::default())));
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day16::parse("Valve AA has flow rate=0; tunnels lead to valves BB\nValve BB has flow rate=x; tunnel leads to valve AA\n").unwrap_err();
+        assert_eq!("expected `Valve XX has flow rate=N; tunnels lead to valves YY, ZZ` at line 2, column 24: `x; tunnel leads to valve AA`", err.to_string());
+        let err = Day16::parse("Valve AA has flow rate=0; tunnels lead to valves BB, CC\nValve BB has flow rate=1; tunnel leads to valve AA\n").unwrap_err();
+        assert_eq!("tunnel leads to an unknown valve at line 1, column 54: `CC`", err.to_string());
+    }
+}
@@ -0,0 +1,250 @@
+use std::collections::HashMap;
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day17/input17.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Empty,
+    Jet(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty jet pattern"),
+            ParseError::Jet(loc) => write!(f, "expected `<` or `>` at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Jet {
+    Left,
+    Right,
+}
+
+const WIDTH: usize = 7;
+const LEFT_WALL: u8 = 1 << (WIDTH - 1);
+const RIGHT_WALL: u8 = 1;
+
+/// The five rock shapes, bottom row first, one bit per column with the leftmost
+/// column in the high bit, already placed two units away from the left wall.
+const SHAPES: [&[u8]; 5] = [
+    &[0b0011110],
+    &[0b0001000, 0b0011100, 0b0001000],
+    &[0b0011100, 0b0000100, 0b0000100],
+    &[0b0010000, 0b0010000, 0b0010000, 0b0010000],
+    &[0b0011000, 0b0011000],
+];
+
+/// A rock that is still falling, its bottom row `y` rows above the floor.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Rock {
+    pub rows: Vec<u8>,
+    pub y: usize,
+}
+
+impl Rock {
+    fn pushed(&self, jet: Jet) -> Option<Vec<u8>> {
+        match jet {
+            Jet::Left if self.rows.iter().all(|row| row & LEFT_WALL == 0) => Some(self.rows.iter().map(|row| row << 1).collect()),
+            Jet::Right if self.rows.iter().all(|row| row & RIGHT_WALL == 0) => Some(self.rows.iter().map(|row| row >> 1).collect()),
+            _ => None,
+        }
+    }
+}
+
+/// The tower of settled rocks plus the rock currently falling, advanced one
+/// jet push and fall at a time.
+#[derive(Debug, Clone)]
+pub struct Chamber<'a> {
+    jets: &'a [Jet],
+    rows: Vec<u8>,
+    jet: usize,
+    rocks: usize,
+    falling: Option<Rock>,
+}
+
+impl<'a> Chamber<'a> {
+    pub fn new(jets: &'a [Jet]) -> Self {
+        Self { jets, rows: Vec::new(), jet: 0, rocks: 0, falling: None }
+    }
+    /// Height of the tower of settled rocks.
+    pub fn height(&self) -> usize {
+        self.rows.len()
+    }
+    /// Number of rocks that have come to rest.
+    pub fn rocks(&self) -> usize {
+        self.rocks
+    }
+    pub fn falling(&self) -> Option<&Rock> {
+        self.falling.as_ref()
+    }
+    /// Settled rocks, bottom row first.
+    pub fn rows(&self) -> &[u8] {
+        &self.rows
+    }
+    fn fits(&self, rows: &[u8], y: usize) -> bool {
+        rows.iter().enumerate().all(|(dy, row)| self.rows.get(y + dy).is_none_or(|settled| settled & row == 0))
+    }
+    /// Spawns the next rock if none is falling; otherwise pushes it with the next
+    /// jet and lets it fall one unit. Returns `true` once the rock comes to rest.
+    pub fn step(&mut self) -> bool {
+        let Some(mut rock) = self.falling.take() else {
+            self.falling = Some(Rock { rows: SHAPES[self.rocks % SHAPES.len()].to_vec(), y: self.height() + 3 });
+            return false;
+        };
+        let jet = self.jets[self.jet];
+        self.jet = (self.jet + 1) % self.jets.len();
+        if let Some(rows) = rock.pushed(jet).filter(|rows| self.fits(rows, rock.y)) {
+            rock.rows = rows;
+        }
+        if rock.y > 0 && self.fits(&rock.rows, rock.y - 1) {
+            rock.y -= 1;
+            self.falling = Some(rock);
+            return false;
+        }
+        for (dy, row) in rock.rows.iter().enumerate() {
+            match self.rows.get_mut(rock.y + dy) {
+                Some(settled) => *settled |= row,
+                None => self.rows.push(*row),
+            }
+        }
+        self.rocks += 1;
+        true
+    }
+    /// Runs until the next rock comes to rest.
+    pub fn drop_rock(&mut self) {
+        while !self.step() {}
+    }
+    /// How far below the top of the tower the highest rock in each column is.
+    fn profile(&self) -> [usize; WIDTH] {
+        std::array::from_fn(|col| {
+            let bit = LEFT_WALL >> col;
+            self.rows.iter().rev().position(|row| row & bit != 0).unwrap_or(self.height())
+        })
+    }
+}
+
+impl fmt::Display for Chamber<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let top = self.falling.as_ref().map_or(0, |rock| rock.y + rock.rows.len()).max(self.height());
+        for y in (0..top).rev() {
+            let settled = self.rows.get(y).copied().unwrap_or(0);
+            let falling = self.falling.as_ref().and_then(|rock| rock.rows.get(y.checked_sub(rock.y)?)).copied().unwrap_or(0);
+            write!(f, "|")?;
+            for col in 0..WIDTH {
+                let bit = LEFT_WALL >> col;
+                let c = if falling & bit != 0 { '@' } else if settled & bit != 0 { '#' } else { '.' };
+                write!(f, "{c}")?;
+            }
+            writeln!(f, "|")?;
+        }
+        writeln!(f, "+{}+", "-".repeat(WIDTH))
+    }
+}
+
+/// Height of the tower after `rocks` rocks. Once the same rock shape, jet position
+/// and surface profile come around again, the repeating cycles are skipped.
+fn tower_height(jets: &[Jet], rocks: usize) -> usize {
+    let mut chamber = Chamber::new(jets);
+    let mut seen = HashMap::new();
+    let mut skipped = 0;
+    while chamber.rocks() < rocks {
+        chamber.drop_rock();
+        if skipped > 0 { continue }
+        let key = (chamber.rocks() % SHAPES.len(), chamber.jet, chamber.profile());
+        if let Some((prev_rocks, prev_height)) = seen.insert(key, (chamber.rocks(), chamber.height())) {
+            let cycle = chamber.rocks() - prev_rocks;
+            let cycles = (rocks - chamber.rocks()) / cycle;
+            skipped = cycles * (chamber.height() - prev_height);
+            chamber.rocks += cycles * cycle;
+        }
+    }
+    chamber.height() + skipped
+}
+
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Parsed<'a> = Vec<Jet>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Jet>> {
+        let line = input.lines().next().filter(|l| !l.is_empty()).ok_or(ParseError::Empty)?;
+        let jets = line.char_indices().map(|(idx, c)| match c {
+            '<' => Ok(Jet::Left),
+            '>' => Ok(Jet::Right),
+            _ => Err(ParseError::Jet(Location::of(input, &line[idx..idx + c.len_utf8()]))),
+        }).collect::<Result<_, _>>()?;
+        Ok(jets)
+    }
+
+    fn part1(jets: &Vec<Jet>, _: &()) -> usize {
+        let mut chamber = Chamber::new(jets);
+        (0..2022).for_each(|_| chamber.drop_rock());
+        chamber.height()
+    }
+
+    fn part2(jets: &Vec<Jet>, _: &()) -> usize {
+        tower_height(jets, 1_000_000_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day17/sample17.txt");
+
+    #[test_case(SAMPLE => 3068 ; "sample")]
+    fn test_part1(input: &str) -> usize {
+        Day17::part1(&Day17::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 1514285714288 ; "sample")]
+    fn test_part2(input: &str) -> usize {
+        Day17::part2(&Day17::parse(input).unwrap(), &())
+    }
+
+    #[test]
+    fn test_steps() {
+        let jets = Day17::parse(SAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        chamber.step();
+        assert_eq!("|..@@@@.|\n|.......|\n|.......|\n|.......|\n+-------+\n", chamber.to_string());
+        chamber.step();
+        assert_eq!("|...@@@@|\n|.......|\n|.......|\n+-------+\n", chamber.to_string());
+        chamber.drop_rock();
+        assert_eq!("|..####.|\n+-------+\n", chamber.to_string());
+        chamber.drop_rock();
+        assert_eq!("|...#...|\n|..###..|\n|...#...|\n|..####.|\n+-------+\n", chamber.to_string());
+        assert_eq!((2, 4), (chamber.rocks(), chamber.height()));
+    }
+
+    #[test_case(2022 ; "part 1")]
+    #[test_case(5000 ; "after several cycles")]
+    fn test_cycle_detection(rocks: usize) {
+        let jets = Day17::parse(SAMPLE).unwrap();
+        let mut chamber = Chamber::new(&jets);
+        (0..rocks).for_each(|_| chamber.drop_rock());
+        assert_eq!(chamber.height(), tower_height(&jets, rocks));
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day17::parse(">><x>\n").unwrap_err();
+        assert_eq!("expected `<` or `>` at line 1, column 4: `x`", err.to_string());
+        let err = Day17::parse("\n").unwrap_err();
+        assert_eq!("empty jet pattern", err.to_string());
+    }
+}
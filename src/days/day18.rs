@@ -0,0 +1,157 @@
+use std::collections::HashSet;
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{geom::Point3, parse::Location, runner::Solution, search::bfs_distances};
+
+pub const INPUT: &str = include_str!("../../bin/day18/input18.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Cube(Location),
+    Coordinate(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Cube(loc) => write!(f, "expected a cube like `2,2,2` at {loc}"),
+            ParseError::Coordinate(loc) => write!(f, "invalid coordinate at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// The lava droplet as a set of unit cubes.
+#[derive(Debug, Clone)]
+pub struct Droplet {
+    cubes: HashSet<Point3>,
+    min: Point3,
+    max: Point3,
+}
+
+impl Droplet {
+    pub fn new(cubes: HashSet<Point3>) -> Self {
+        let min = cubes.iter().copied().reduce(Point3::min).unwrap_or_default();
+        let max = cubes.iter().copied().reduce(Point3::max).unwrap_or_default();
+        Self { cubes, min, max }
+    }
+    pub fn contains(&self, cube: Point3) -> bool {
+        self.cubes.contains(&cube)
+    }
+    /// Whether `p` lies in the bounding box grown by `margin` on every side.
+    fn in_bounds(&self, p: Point3, margin: i64) -> bool {
+        (self.min.x - margin..=self.max.x + margin).contains(&p.x)
+            && (self.min.y - margin..=self.max.y + margin).contains(&p.y)
+            && (self.min.z - margin..=self.max.z + margin).contains(&p.z)
+    }
+    /// Faces not touching another cube, including those facing trapped air.
+    pub fn surface_area(&self) -> usize {
+        self.cubes.iter().flat_map(|c| c.neighbours()).filter(|n| !self.contains(*n)).count()
+    }
+    /// Air reachable from outside the droplet, flood filled through the bounding
+    /// box grown by one so the fill can wrap around every side.
+    pub fn exterior(&self) -> HashSet<Point3> {
+        let start = self.min - Point3::new(1, 1, 1);
+        bfs_distances(start, |p| self.air_around(*p, 1)).into_keys().collect()
+    }
+    fn air_around(&self, p: Point3, margin: i64) -> impl Iterator<Item = Point3> + '_ {
+        p.neighbours().into_iter().filter(move |&n| !self.contains(n) && self.in_bounds(n, margin))
+    }
+    /// Faces touching air that can escape the droplet.
+    pub fn exterior_surface_area(&self) -> usize {
+        let exterior = self.exterior();
+        self.cubes.iter().flat_map(|c| c.neighbours()).filter(|n| exterior.contains(n)).count()
+    }
+    /// Trapped air pockets, each a connected set of cells sorted by position.
+    /// Pockets are ordered by their first cell.
+    pub fn pockets(&self) -> Vec<Vec<Point3>> {
+        let exterior = self.exterior();
+        let mut seen = HashSet::new();
+        let mut pockets = Vec::new();
+        for x in self.min.x..=self.max.x {
+            for y in self.min.y..=self.max.y {
+                for z in self.min.z..=self.max.z {
+                    let p = Point3::new(x, y, z);
+                    if self.contains(p) || exterior.contains(&p) || seen.contains(&p) { continue }
+                    let mut pocket: Vec<Point3> = bfs_distances(p, |p| self.air_around(*p, 0)).into_keys().collect();
+                    pocket.sort_unstable();
+                    seen.extend(pocket.iter().copied());
+                    pockets.push(pocket);
+                }
+            }
+        }
+        pockets
+    }
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Parsed<'a> = Droplet;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Droplet> {
+        let cubes = input.lines().map(|line| {
+            let coords: Vec<&str> = line.split(',').collect();
+            let [x, y, z] = coords[..] else {
+                return Err(ParseError::Cube(Location::of(input, line)));
+            };
+            let coord = |c: &str| c.trim().parse::<i64>().map_err(|_| ParseError::Coordinate(Location::of(input, c)));
+            Ok(Point3::new(coord(x)?, coord(y)?, coord(z)?))
+        }).collect::<Result<_, _>>()?;
+        Ok(Droplet::new(cubes))
+    }
+
+    fn part1(droplet: &Droplet, _: &()) -> usize {
+        droplet.surface_area()
+    }
+
+    fn part2(droplet: &Droplet, _: &()) -> usize {
+        droplet.exterior_surface_area()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day18/sample18.txt");
+
+    #[test_case("1,1,1\n2,1,1\n" => 10 ; "two cubes")]
+    #[test_case(SAMPLE => 64 ; "sample")]
+    fn test_part1(input: &str) -> usize {
+        Day18::part1(&Day18::parse(input).unwrap(), &())
+    }
+
+    #[test_case("1,1,1\n2,1,1\n" => 10 ; "two cubes")]
+    #[test_case(SAMPLE => 58 ; "sample")]
+    fn test_part2(input: &str) -> usize {
+        Day18::part2(&Day18::parse(input).unwrap(), &())
+    }
+
+    #[test]
+    fn test_pockets() {
+        assert_eq!(vec![vec![Point3::new(2, 2, 5)]], Day18::parse(SAMPLE).unwrap().pockets());
+
+        // A solid 3x3x5 block with two separate single-cell cavities.
+        let block: HashSet<Point3> = (0..3).flat_map(|x| (0..3).flat_map(move |y| (0..5).map(move |z| Point3::new(x, y, z))))
+            .filter(|&p| p != Point3::new(1, 1, 1) && p != Point3::new(1, 1, 3))
+            .collect();
+        let droplet = Droplet::new(block);
+        assert_eq!(vec![vec![Point3::new(1, 1, 1)], vec![Point3::new(1, 1, 3)]], droplet.pockets());
+        assert_eq!(droplet.surface_area() - 12, droplet.exterior_surface_area());
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day18::parse("1,1,1\n2,1\n").unwrap_err();
+        assert_eq!("expected a cube like `2,2,2` at line 2, column 1: `2,1`", err.to_string());
+        let err = Day18::parse("1,1,1\n2,x,1\n").unwrap_err();
+        assert_eq!("invalid coordinate at line 2, column 3: `x`", err.to_string());
+    }
+}
@@ -0,0 +1,231 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::{unsigned, Location}, runner::Solution};
+use nom::{
+    IResult,
+    bytes::complete::tag,
+    sequence::tuple,
+    combinator::map,
+};
+
+pub const INPUT: &str = include_str!("../../bin/day19/input19.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Blueprint(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Blueprint(loc) => write!(f, "expected `Blueprint N: Each ore robot costs ...` at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Resource {
+    Ore,
+    Clay,
+    Obsidian,
+    Geode,
+}
+
+impl Resource {
+    const ALL: [Resource; 4] = [Resource::Ore, Resource::Clay, Resource::Obsidian, Resource::Geode];
+}
+
+/// What each robot costs, in ore, clay and obsidian.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Blueprint {
+    pub id: u32,
+    costs: [[u32; 3]; 4],
+}
+
+/// The best plan found: the geodes it opens and the robot whose construction
+/// starts in each minute, if any.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Plan {
+    pub geodes: u32,
+    pub builds: Vec<Option<Resource>>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct State {
+    robots: [u32; 4],
+    stock: [u32; 4],
+    time_left: u32,
+}
+
+impl Blueprint {
+    /// Beyond this many robots of a kind the extra production can never be spent,
+    /// since only one robot is built per minute.
+    fn max_robots(&self, robot: Resource) -> u32 {
+        match robot {
+            Resource::Geode => u32::MAX,
+            _ => self.costs.iter().map(|cost| cost[robot as usize]).max().unwrap_or(0),
+        }
+    }
+    /// Minutes to wait until `robot` is affordable, or `None` if nothing produces
+    /// one of its ingredients yet.
+    fn wait_for(&self, robot: Resource, state: &State) -> Option<u32> {
+        self.costs[robot as usize].iter().enumerate().map(|(res, &cost)| {
+            let missing = cost.saturating_sub(state.stock[res]);
+            match (missing, state.robots[res]) {
+                (0, _) => Some(0),
+                (_, 0) => None,
+                (missing, robots) => Some(missing.div_ceil(robots)),
+            }
+        }).try_fold(0, |acc, wait| Some(acc.max(wait?)))
+    }
+    /// Branch and bound over which robot to build next, skipping straight to the
+    /// minute it becomes affordable.
+    fn search(&self, state: State, builds: &mut Vec<Option<Resource>>, best: &mut Plan) {
+        let t = state.time_left;
+        let idle = state.stock[3] + state.robots[3] * t;
+        if idle > best.geodes || best.builds.is_empty() {
+            best.geodes = idle;
+            best.builds.clone_from(builds);
+            best.builds.resize(builds.len() + t as usize, None);
+        }
+        // Optimistic bound: a new geode robot every remaining minute.
+        if idle + t * t.saturating_sub(1) / 2 <= best.geodes {
+            return;
+        }
+        for robot in Resource::ALL.into_iter().rev() {
+            if state.robots[robot as usize] >= self.max_robots(robot) { continue }
+            let Some(wait) = self.wait_for(robot, &state).filter(|wait| wait + 1 < t) else { continue };
+            let mut next = state;
+            for res in 0..4 {
+                next.stock[res] += state.robots[res] * (wait + 1);
+            }
+            for (res, cost) in self.costs[robot as usize].iter().enumerate() {
+                next.stock[res] -= cost;
+            }
+            next.robots[robot as usize] += 1;
+            next.time_left = t - wait - 1;
+            let len = builds.len();
+            builds.resize(len + wait as usize, None);
+            builds.push(Some(robot));
+            self.search(next, builds, best);
+            builds.truncate(len);
+        }
+    }
+    /// The plan opening the most geodes within `minutes`, starting with one ore robot.
+    pub fn best_plan(&self, minutes: u32) -> Plan {
+        let mut best = Plan::default();
+        let start = State { robots: [1, 0, 0, 0], stock: [0; 4], time_left: minutes };
+        self.search(start, &mut Vec::new(), &mut best);
+        best
+    }
+    /// Replays a build order minute by minute, returning the geodes opened or
+    /// `None` if some robot was started before it was affordable.
+    pub fn simulate(&self, builds: &[Option<Resource>]) -> Option<u32> {
+        let (mut robots, mut stock): ([u32; 4], [u32; 4]) = ([1, 0, 0, 0], [0; 4]);
+        for build in builds {
+            if let Some(robot) = build {
+                for (res, cost) in self.costs[*robot as usize].iter().enumerate() {
+                    stock[res] = stock[res].checked_sub(*cost)?;
+                }
+            }
+            for res in 0..4 {
+                stock[res] += robots[res];
+            }
+            if let Some(robot) = build {
+                robots[*robot as usize] += 1;
+            }
+        }
+        Some(stock[3])
+    }
+}
+
+fn blueprint_parser(input: &str) -> IResult<&str, Blueprint> {
+    map(tuple((
+        tag("Blueprint "), unsigned,
+        tag(": Each ore robot costs "), unsigned,
+        tag(" ore. Each clay robot costs "), unsigned,
+        tag(" ore. Each obsidian robot costs "), unsigned, tag(" ore and "), unsigned,
+        tag(" clay. Each geode robot costs "), unsigned, tag(" ore and "), unsigned, tag(" obsidian."),
+    )), |(_, id, _, ore, _, clay, _, obs_ore, _, obs_clay, _, geo_ore, _, geo_obs, _)| Blueprint {
+        id,
+        costs: [[ore, 0, 0], [clay, 0, 0], [obs_ore, obs_clay, 0], [geo_ore, 0, geo_obs]],
+    })(input)
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Parsed<'a> = Vec<Blueprint>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<Blueprint>> {
+        let blueprints = input.lines().map(|line| match blueprint_parser(line) {
+            Ok(("", blueprint)) => Ok(blueprint),
+            Ok((rest, _)) => Err(ParseError::Blueprint(Location::of(input, rest))),
+            Err(e) => Err(ParseError::Blueprint(Location::of(input, crate::parse::unparsed(&e, line)))),
+        }).collect::<Result<_, _>>()?;
+        Ok(blueprints)
+    }
+
+    fn part1(blueprints: &Vec<Blueprint>, _: &()) -> u32 {
+        blueprints.iter().map(|bp| bp.id * bp.best_plan(24).geodes).sum()
+    }
+
+    fn part2(blueprints: &Vec<Blueprint>, _: &()) -> u32 {
+        blueprints.iter().take(3).map(|bp| bp.best_plan(32).geodes).product()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day19/sample19.txt");
+
+    #[test_case(SAMPLE => 33 ; "sample")]
+    fn test_part1(input: &str) -> u32 {
+        Day19::part1(&Day19::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 56 * 62 ; "sample")]
+    fn test_part2(input: &str) -> u32 {
+        Day19::part2(&Day19::parse(input).unwrap(), &())
+    }
+
+    #[test_case(0, 24 => 9 ; "blueprint 1 in 24 minutes")]
+    #[test_case(1, 24 => 12 ; "blueprint 2 in 24 minutes")]
+    #[test_case(0, 32 => 56 ; "blueprint 1 in 32 minutes")]
+    fn test_build_order(idx: usize, minutes: u32) -> u32 {
+        let blueprint = &Day19::parse(SAMPLE).unwrap()[idx];
+        let plan = blueprint.best_plan(minutes);
+        assert_eq!(minutes as usize, plan.builds.len());
+        assert_eq!(Some(plan.geodes), blueprint.simulate(&plan.builds));
+        plan.geodes
+    }
+
+    #[test]
+    fn test_simulate() {
+        use Resource::*;
+        let blueprint = &Day19::parse(SAMPLE).unwrap()[0];
+        // The build order from the puzzle's walkthrough of blueprint 1.
+        let mut builds = vec![None; 24];
+        for (minute, robot) in [(3, Clay), (5, Clay), (7, Clay), (11, Obsidian), (12, Clay), (15, Obsidian), (18, Geode), (21, Geode)] {
+            builds[minute - 1] = Some(robot);
+        }
+        assert_eq!(Some(9), blueprint.simulate(&builds));
+        builds[0] = Some(Geode);
+        assert_eq!(None, blueprint.simulate(&builds));
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day19::parse("Blueprint 1: Each ore robot costs 4 gold.\n").unwrap_err();
+        assert_eq!("expected `Blueprint N: Each ore robot costs ...` at line 1, column 36: ` gold.`", err.to_string());
+    }
+}
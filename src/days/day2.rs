@@ -0,0 +1,131 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day2/input2.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Opponent(Location),
+    Response(Location),
+    Game(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Opponent(loc) => write!(f, "expected A, B or C at {loc}"),
+            ParseError::Response(loc) => write!(f, "expected X, Y or Z at {loc}"),
+            ParseError::Game(loc) => write!(f, "expected two shapes separated by a space at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn char_to_score(c: char) -> i32 {
+    match c {
+        'A' | 'X' => 1,
+        'B' | 'Y' => 2,
+        'C' | 'Z' => 3,
+        _ => 0,
+    }
+}
+
+fn score_guide(games: &[(char, char)]) -> i32 {
+    let mut score = 0;
+    games.iter().for_each(|&(op, you)| {
+        let op = char_to_score(op);
+        let you = char_to_score(you);
+        match (op - you).rem_euclid(3) {
+            2 => score += 6 + you,
+            1 => score += you,
+            _ => score += 3 + you,
+        }
+    });
+    score
+}
+
+fn char_to_outcome(c: char, score: &mut i32) -> i32 {
+    match c {
+        'X' => 1,
+        'Y' => {
+            *score += 3;
+            0
+        },
+        'Z' => {
+            *score += 6;
+            2
+        },
+        _ => 0,
+    }
+}
+
+fn score_outcomes(games: &[(char, char)]) -> i32 {
+    let mut score = 0;
+    games.iter().for_each(|&(op, outcome)| {
+        let op = char_to_score(op);
+        let outcome = char_to_outcome(outcome, &mut score);
+        match (op - outcome).rem_euclid(3) {
+            0 => score += 3,
+            1 => score += 1,
+            _ => score += 2,
+        }
+    });
+    score
+}
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Parsed<'a> = Vec<(char, char)>;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Vec<(char, char)>> {
+        let games = input.lines().map(|game| {
+            let (op, you) = game.split_once(' ').ok_or_else(|| ParseError::Game(Location::of(input, game)))?;
+            if !matches!(op, "A" | "B" | "C") {
+                return Err(ParseError::Opponent(Location::of(input, op)));
+            }
+            if !matches!(you, "X" | "Y" | "Z") {
+                return Err(ParseError::Response(Location::of(input, you)));
+            }
+            Ok((op.as_bytes()[0] as char, you.as_bytes()[0] as char))
+        }).collect::<Result<_, _>>()?;
+        Ok(games)
+    }
+
+    fn part1(games: &Vec<(char, char)>, _: &()) -> i32 {
+        score_guide(games)
+    }
+
+    fn part2(games: &Vec<(char, char)>, _: &()) -> i32 {
+        score_outcomes(games)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day2/sample2.txt");
+
+    #[test_case(SAMPLE => 15 ; "sample")]
+    fn test_part1(input: &str) -> i32 {
+        Day2::part1(&Day2::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 12 ; "sample")]
+    fn test_part2(input: &str) -> i32 {
+        Day2::part2(&Day2::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day2::parse("A Y\nB W\n").unwrap_err();
+        assert_eq!("expected X, Y or Z at line 2, column 3: `W`", err.to_string());
+    }
+}
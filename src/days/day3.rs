@@ -0,0 +1,113 @@
+use std::collections::HashMap;
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day3/input3.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Item(Location),
+    Compartments(Location),
+    NoCommonItem(Location),
+    Group(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Item(loc) => write!(f, "items must be ASCII letters at {loc}"),
+            ParseError::Compartments(loc) => write!(f, "rucksack cannot be split into two equal compartments at {loc}"),
+            ParseError::NoCommonItem(loc) => write!(f, "compartments share no item at {loc}"),
+            ParseError::Group(loc) => write!(f, "rucksacks must come in groups of three sharing a badge at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn priorities() -> HashMap<char, i32> {
+    (('a'..='z').chain('A'..='Z')).zip(1..=52).collect()
+}
+
+/// Priorities of the item shared by each rucksack's compartments and of each
+/// group's badge.
+#[derive(Debug)]
+pub struct Rucksacks {
+    pub shared: Vec<i32>,
+    pub badges: Vec<i32>,
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Parsed<'a> = Rucksacks;
+    type Params = ();
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Rucksacks> {
+        let prs = priorities();
+        let lines: Vec<&str> = input.lines().collect();
+        let mut shared = Vec::new();
+        for line in &lines {
+            if let Some((idx, c)) = line.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+                return Err(ParseError::Item(Location::of(input, &line[idx..idx + c.len_utf8()])).into());
+            }
+            if line.len() % 2 != 0 {
+                return Err(ParseError::Compartments(Location::of(input, line)).into());
+            }
+            let (left, right) = line.split_at(line.len() / 2);
+            let matched = left.chars().find(|&x| right.contains(x))
+                .ok_or_else(|| ParseError::NoCommonItem(Location::of(input, line)))?;
+            shared.push(prs[&matched]);
+        }
+        let badges = lines.chunks(3).map(|group| match group {
+            [l1, l2, l3] => l1.chars().find(|&x| l2.contains(x) && l3.contains(x)).map(|x| prs[&x]),
+            _ => None,
+        }.ok_or_else(|| ParseError::Group(Location::of(input, group[0])))).collect::<Result<_, _>>()?;
+        Ok(Rucksacks { shared, badges })
+    }
+
+    fn part1(sacks: &Rucksacks, _: &()) -> i32 {
+        sacks.shared.iter().sum()
+    }
+
+    fn part2(sacks: &Rucksacks, _: &()) -> i32 {
+        sacks.badges.iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day3/sample3.txt");
+
+    #[test_case(SAMPLE => 157 ; "sample")]
+    fn test_part1(input: &str) -> i32 {
+        Day3::part1(&Day3::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 70 ; "sample")]
+    fn test_part2(input: &str) -> i32 {
+        Day3::part2(&Day3::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day3::parse("abAb\nab1B\n").unwrap_err();
+        assert_eq!("items must be ASCII letters at line 2, column 3: `1`", err.to_string());
+        let err = Day3::parse("abAB\n").unwrap_err();
+        assert_eq!("compartments share no item at line 1, column 1: `abAB`", err.to_string());
+    }
+
+    #[test_case("aa\nbb\ncc\n", "line 1, column 1: `aa`" ; "no badge")]
+    #[test_case("aa\naa\naa\nbb\n", "line 4, column 1: `bb`" ; "incomplete group")]
+    fn test_group_error(input: &str, loc: &str) {
+        let err = Day3::parse(input).unwrap_err();
+        assert_eq!(format!("rucksacks must come in groups of three sharing a badge at {loc}"), err.to_string());
+    }
+}
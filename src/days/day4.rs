@@ -0,0 +1,84 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day4/input4.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    SectionId(Location),
+    Pair(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::SectionId(loc) => write!(f, "invalid section id at {loc}"),
+            ParseError::Pair(loc) => write!(f, "expected two ranges like `2-4,6-8` at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Parsed<'a> = Vec<[u8; 4]>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<[u8; 4]>> {
+        let pairs = input.lines().map(|line| {
+            let r: Vec<u8> = line.split(['-', ',']).map(|x| {
+                x.parse().map_err(|_| ParseError::SectionId(Location::of(input, x)))
+            }).collect::<Result<_, _>>()?;
+            r.try_into().map_err(|_| ParseError::Pair(Location::of(input, line)))
+        }).collect::<Result<_, _>>()?;
+        Ok(pairs)
+    }
+
+    fn part1(pairs: &Vec<[u8; 4]>, _: &()) -> usize {
+        pairs.iter().filter(|r| {
+            let range1 = r[0]..=r[1];
+            let range2 = r[2]..=r[3];
+
+            range1.clone().all(|x| range2.contains(&x)) || range2.clone().all(|x| range1.contains(&x))
+        }).count()
+    }
+
+    fn part2(pairs: &Vec<[u8; 4]>, _: &()) -> usize {
+        pairs.iter().filter(|r| {
+            let range1 = r[0]..=r[1];
+            let range2 = r[2]..=r[3];
+
+            range1.clone().any(|x| range2.contains(&x))
+        }).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day4/sample4.txt");
+
+    #[test_case(SAMPLE => 2 ; "sample")]
+    fn test_part1(input: &str) -> usize {
+        Day4::part1(&Day4::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 4 ; "sample")]
+    fn test_part2(input: &str) -> usize {
+        Day4::part2(&Day4::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day4::parse("2-4,6-8\n2-3,x-5\n").unwrap_err();
+        assert_eq!("invalid section id at line 2, column 5: `x`", err.to_string());
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::{BTreeMap, VecDeque};
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+use nom::{
+    branch::alt,
+    bytes::complete::{tag, take},
+    combinator::{map, opt},
+    sequence::{delimited, preceded},
+    IResult,
+};
+
+pub const INPUT: &str = include_str!("../../bin/day5/input5.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Layout,
+    CrateRow(Location),
+    Move(Location),
+    Stack(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Layout => write!(f, "expected a crate drawing and a list of moves separated by a blank line"),
+            ParseError::CrateRow(loc) => write!(f, "expected `[X]` or three spaces at {loc}"),
+            ParseError::Move(loc) => write!(f, "expected `move N from A to B` at {loc}"),
+            ParseError::Stack(loc) => write!(f, "no such stack at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+fn parse_crate(i: &str) -> IResult<&str, char> {
+    let first_char = |s: &str| s.chars().next().unwrap();
+    let f = delimited(tag("["), take(1_usize), tag("]"));
+    map(f, first_char)(i)
+}
+
+fn parse_hole(i: &str) -> IResult<&str, ()> {
+    map(tag("   "), drop)(i)
+}
+
+fn parse_crate_or_hole(i: &str) -> IResult<&str, Option<char>> {
+    alt((map(parse_crate, Some), map(parse_hole, |_| None)))(i)
+}
+
+fn parse_crate_line(i: &str) -> IResult<&str, Vec<Option<char>>> {
+    let (mut i, c) = parse_crate_or_hole(i)?;
+    let mut v = vec![c];
+
+    loop {
+        let (next_i, maybe_c) = opt(preceded(tag(" "), parse_crate_or_hole))(i)?;
+        match maybe_c {
+            Some(c) => v.push(c),
+            None => break,
+        }
+        i = next_i;
+    }
+
+    Ok((i, v))
+}
+
+#[derive(Debug, Clone)]
+pub struct Crane {
+    crts: BTreeMap<usize, Vec<char>>,
+    cmds: VecDeque<Vec<usize>>,
+}
+
+impl Crane {
+    fn new() -> Self {
+        Self {
+            crts: BTreeMap::new(),
+            cmds: VecDeque::new(),
+        }
+    }
+    fn move_crates_p1(&mut self) {
+        while let Some(cmd) = self.cmds.pop_front() {
+            let (qt, fr, to) = (cmd[0], cmd[1], cmd[2]);
+            (0..qt).for_each(|_| {
+                if let Some(item) = self.crts.get_mut(&fr).and_then(|from| from.pop()) {
+                    self.crts.get_mut(&to).unwrap().push(item);
+                }
+            })
+        }
+    }
+    fn move_crates_p2(&mut self) {
+        while let Some(cmd) = self.cmds.pop_front() {
+            let (qt, fr, to) = (cmd[0], cmd[1], cmd[2]);
+            let mut temp = Vec::new();
+            (0..qt).for_each(|_| {
+                if let Some(item) = self.crts.get_mut(&fr).and_then(|from| from.pop()) {
+                    temp.push(item);
+                }
+            });
+            temp.reverse();
+            self.crts.get_mut(&to).unwrap().extend(temp);
+        }
+    }
+    fn final_cfg(&self) -> String {
+        self.crts.values().filter_map(|v| v.last()).collect::<String>()
+    }
+}
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Parsed<'a> = Crane;
+    type Params = ();
+    type Answer1 = String;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Crane> {
+        let [crts, cmds] = crate::parse::blocks(input)[..] else {
+            return Err(ParseError::Layout.into());
+        };
+        let mut crane = Crane::new();
+        for line in crts.lines().rev().skip(1) {
+            let row = match parse_crate_line(line) {
+                Ok((rest, row)) if rest.trim().is_empty() => row,
+                Ok((rest, _)) => return Err(ParseError::CrateRow(Location::of(input, rest)).into()),
+                Err(e) => return Err(ParseError::CrateRow(Location::of(input, crate::parse::unparsed(&e, line))).into()),
+            };
+            row.into_iter().enumerate().for_each(|(idx, c)| {
+                let stack = crane.crts.entry(idx + 1).or_default();
+                if let Some(ch) = c {
+                    stack.push(ch);
+                }
+            });
+        }
+        for line in cmds.lines() {
+            let words: Vec<&str> = line.split_whitespace().collect();
+            let ["move", qt, "from", fr, "to", to] = words[..] else {
+                return Err(ParseError::Move(Location::of(input, line)).into());
+            };
+            let cmd_ln = [qt, fr, to].iter().map(|x| {
+                x.parse::<usize>().map_err(|_| ParseError::Move(Location::of(input, x)))
+            }).collect::<Result<Vec<_>, _>>()?;
+            for (id, x) in cmd_ln[1..].iter().zip([fr, to]) {
+                if !crane.crts.contains_key(id) {
+                    return Err(ParseError::Stack(Location::of(input, x)).into());
+                }
+            }
+            crane.cmds.push_back(cmd_ln)
+        }
+        Ok(crane)
+    }
+
+    fn part1(crane: &Crane, _: &()) -> String {
+        let mut crane = crane.clone();
+        crane.move_crates_p1();
+        crane.final_cfg()
+    }
+
+    fn part2(crane: &Crane, _: &()) -> String {
+        let mut crane = crane.clone();
+        crane.move_crates_p2();
+        crane.final_cfg()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day5/sample5.txt");
+
+    #[test_case(SAMPLE => "CMZ".to_string() ; "sample")]
+    fn test_part1(input: &str) -> String {
+        Day5::part1(&Day5::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => "MCD".to_string() ; "sample")]
+    fn test_part2(input: &str) -> String {
+        Day5::part2(&Day5::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day5::parse("[A] [B]\n 1   2\n\nmove 1 from 1 to 3\n").unwrap_err();
+        assert_eq!("no such stack at line 4, column 18: `3`", err.to_string());
+        let err = Day5::parse("[A] [B]\n 1   2\n").unwrap_err();
+        assert_eq!("expected a crate drawing and a list of moves separated by a blank line", err.to_string());
+    }
+}
@@ -0,0 +1,90 @@
+use std::fmt;
+use itertools::Itertools;
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day6/input6.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Empty,
+    Char(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Empty => write!(f, "empty datastream"),
+            ParseError::Char(loc) => write!(f, "datastream must be lowercase letters at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Characters read up to the end of the first `len` distinct ones in a row, or 0
+/// if the signal has no such marker.
+fn find_marker(signal: &str, len: usize) -> usize {
+    signal.as_bytes().windows(len).position(|win| win.iter().all_unique()).map_or(0, |idx| idx + len)
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Parsed<'a> = &'a str;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<&str> {
+        let signal = input.lines().next().filter(|l| !l.is_empty()).ok_or(ParseError::Empty)?;
+        if let Some((idx, c)) = signal.char_indices().find(|(_, c)| !c.is_ascii_lowercase()) {
+            return Err(ParseError::Char(Location::of(input, &signal[idx..idx + c.len_utf8()])).into());
+        }
+        Ok(signal)
+    }
+
+    fn part1(signal: &&str, _: &()) -> usize {
+        find_marker(signal, 4)
+    }
+
+    fn part2(signal: &&str, _: &()) -> usize {
+        find_marker(signal, 14)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day6/sample6.txt");
+
+    #[test_case(SAMPLE => 7 ; "sample")]
+    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz" => 5 ; "bvwbjplbgvbhsrlpgdmjqwftvncz")]
+    #[test_case("nppdvjthqldpwncqszvftbrmjlhg" => 6 ; "nppdvjthqldpwncqszvftbrmjlhg")]
+    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => 10 ; "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")]
+    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => 11 ; "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")]
+    #[test_case("aaaa" => 0 ; "no marker")]
+    fn test_part1(input: &str) -> usize {
+        Day6::part1(&Day6::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 19 ; "sample")]
+    #[test_case("bvwbjplbgvbhsrlpgdmjqwftvncz" => 23 ; "bvwbjplbgvbhsrlpgdmjqwftvncz")]
+    #[test_case("nppdvjthqldpwncqszvftbrmjlhg" => 23 ; "nppdvjthqldpwncqszvftbrmjlhg")]
+    #[test_case("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg" => 29 ; "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg")]
+    #[test_case("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw" => 26 ; "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw")]
+    #[test_case("abcd" => 0 ; "too short")]
+    fn test_part2(input: &str) -> usize {
+        Day6::part2(&Day6::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day6::parse("abcD").unwrap_err();
+        assert_eq!("datastream must be lowercase letters at line 1, column 4: `D`", err.to_string());
+        assert_eq!("empty datastream", Day6::parse("").unwrap_err().to_string());
+    }
+}
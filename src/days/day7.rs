@@ -0,0 +1,208 @@
+use color_eyre::eyre::Result;
+use crate::{parse::Location, runner::Solution};
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::{
+    rc::{Rc, Weak},
+    cell::RefCell,
+};
+
+pub const INPUT: &str = include_str!("../../bin/day7/input7.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Start(Location),
+    AboveRoot(Location),
+    UnknownDir(Location),
+    Entry(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Start(loc) => write!(f, "expected the session to start with `$ cd /` at {loc}"),
+            ParseError::AboveRoot(loc) => write!(f, "cannot leave the root directory at {loc}"),
+            ParseError::UnknownDir(loc) => write!(f, "no such directory at {loc}"),
+            ParseError::Entry(loc) => write!(f, "expected `dir NAME` or `SIZE NAME` at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+pub struct Node {
+    id: String,
+    parent: RefCell<Weak<Node>>,
+    children: RefCell<Vec<Rc<Node>>>,
+    size: Rc<RefCell<u32>>,
+}
+
+impl Hash for Node {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+    }
+}
+
+impl Node {
+    fn new(id: String) -> Rc<Self> {
+        Rc::new(Node {
+            id,
+            parent: RefCell::new(Weak::new()),
+            children: RefCell::new(vec![]),
+            size: Rc::new(RefCell::new(0)),
+        })
+    }
+
+    fn add_child(parent: &Rc<Self>, id: String) {
+        let child = Rc::new(Node {
+            id,
+            parent: RefCell::new(Rc::downgrade(parent)),
+            children: RefCell::new(vec![]),
+            size: Rc::new(RefCell::new(0)),
+        });
+        parent.children.borrow_mut().push(Rc::clone(&child));
+    }
+    fn get_parent(&self) -> Option<Rc<Node>> {
+        self.parent.borrow().upgrade()
+    }
+
+    fn get_child(&self, id: String) -> Option<Rc<Node>> {
+        for child in self.children.borrow().iter() {
+            if child.id == id {
+                return Some(Rc::clone(child));
+            }
+        }
+        None
+    }
+    fn add_to_size(&self, additional_size: u32) {
+        *self.size.borrow_mut() += additional_size;
+    }
+    fn define_sizes(&self) {
+        *self.size.borrow_mut() += self.children.borrow_mut().iter().map(|child| {
+            child.define_sizes();
+            *child.size.borrow()
+        }).sum::<u32>();
+    }
+    fn added_right_sizes(&self) -> u32 {
+        let mut sum = 0;
+        if *self.size.borrow() <= 100000 {
+            sum += *self.size.borrow();
+        }
+
+        for child in self.children.borrow().iter() {
+            sum += child.added_right_sizes();
+        }
+
+        sum
+    }
+    fn find_cnds(&self, space_to_free: u32) -> Vec<u32> {
+        let mut cnds = Vec::new();
+        if *self.size.borrow() >= space_to_free {
+            cnds.push(*self.size.borrow());
+        }
+        for child in self.children.borrow().iter() {
+            cnds.extend(child.find_cnds(space_to_free));
+        }
+        cnds
+    }
+    #[allow(dead_code)]
+    fn print_tree(&self, indent: usize) {
+        let indent_str = " ".repeat(indent * 4);
+        println!("{} node: {}, size: {}", indent_str, self.id, *self.size.borrow());
+
+        for child in self.children.borrow().iter() {
+            child.print_tree(indent + 1);
+        }
+    }
+}
+
+fn build_tree(input: &str) -> Result<Rc<Node>, ParseError> {
+    let node = Node::new(String::from("/"));
+    let mut cur_node = Rc::clone(&node);
+
+    let mut lines = input.lines();
+    match lines.next() {
+        Some("$ cd /") => {}
+        first => return Err(ParseError::Start(Location::of(input, first.unwrap_or(input)))),
+    }
+    for line in lines {
+        if let Some(name) = line.strip_prefix("dir ") {
+            Node::add_child(&cur_node, name.to_string());
+        } else if line.contains("..") {
+            cur_node = cur_node.get_parent().ok_or_else(|| ParseError::AboveRoot(Location::of(input, line)))?;
+        } else if let Some(name) = line.strip_prefix("$ cd ") {
+            let new_child = cur_node.get_child(name.to_string()).ok_or_else(|| ParseError::UnknownDir(Location::of(input, name)))?;
+            cur_node = new_child;
+        } else if line.contains("$ ls") {
+        } else {
+            let (size, _) = line.split_once(' ').ok_or_else(|| ParseError::Entry(Location::of(input, line)))?;
+            let size = size.parse::<u32>().map_err(|_| ParseError::Entry(Location::of(input, size)))?;
+            Node::add_to_size(&cur_node, size);
+        }
+    }
+    Ok(node)
+}
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Parsed<'a> = Rc<Node>;
+    type Params = ();
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Rc<Node>> {
+        let tree = build_tree(input)?;
+        tree.define_sizes();
+        Ok(tree)
+    }
+
+    fn part1(tree: &Rc<Node>, _: &()) -> u32 {
+        //tree.print_tree(2);
+        tree.added_right_sizes()
+    }
+
+    /// Size of the smallest directory to delete, or 0 if there is already enough space.
+    fn part2(tree: &Rc<Node>, _: &()) -> u32 {
+        let total_space: u32 = 70000000;
+        let needed: u32 = 30000000;
+        let cur_taken = *tree.size.borrow();
+        let space_to_free = needed.saturating_sub(total_space.saturating_sub(cur_taken));
+        if space_to_free == 0 {
+            return 0;
+        }
+        // The root always frees enough, since everything is in it.
+        tree.find_cnds(space_to_free).into_iter().min().unwrap_or(cur_taken)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day7/sample7.txt");
+
+    #[test_case(SAMPLE => 95437 ; "sample")]
+    fn test_part1(input: &str) -> u32 {
+        Day7::part1(&Day7::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 24933642 ; "sample")]
+    #[test_case("$ cd /\n$ ls\n100 a\n" => 0 ; "nothing to free")]
+    #[test_case("$ cd /\n$ ls\n69000000 a\ndir b\n$ cd b\n$ ls\n500 c\n" => 69000500 ; "only the root is big enough")]
+    fn test_part2(input: &str) -> u32 {
+        Day7::part2(&Day7::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day7::parse("$ cd /\n$ ls\ndir a\n$ cd b\n").unwrap_err();
+        assert_eq!("no such directory at line 4, column 6: `b`", err.to_string());
+        let err = Day7::parse("$ ls\n100 a\n").unwrap_err();
+        assert_eq!("expected the session to start with `$ cd /` at line 1, column 1: `$ ls`", err.to_string());
+        let err = Day7::parse("").unwrap_err();
+        assert_eq!("expected the session to start with `$ cd /` at line 1, column 1: ``", err.to_string());
+    }
+}
@@ -0,0 +1,101 @@
+use std::fmt;
+use color_eyre::eyre::Result;
+use crate::{grid::{Grid, GridError}, parse::Location, runner::Solution};
+
+pub const INPUT: &str = include_str!("../../bin/day8/input8.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Height(Location),
+    Width(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Height(loc) => write!(f, "tree heights must be digits at {loc}"),
+            ParseError::Width(loc) => write!(f, "rows must all have the same length at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+pub struct Forest(Grid<u8>);
+
+impl Forest {
+    /// Walks from `(x, y)` in direction `(dx, dy)` and returns whether the edge is
+    /// visible from the tree there and how many trees it can see.
+    fn look(&self, x: usize, y: usize, (dx, dy): (isize, isize)) -> (bool, usize) {
+        let h = self.0[(x, y)];
+        let (mut pos, mut score) = ((x, y), 0);
+        while let Some(next) = pos.0.checked_add_signed(dx).zip(pos.1.checked_add_signed(dy)).filter(|&(nx, ny)| self.0.contains(nx, ny)) {
+            score += 1;
+            if self.0[next] >= h { return (false, score) }
+            pos = next;
+        }
+        (true, score)
+    }
+    fn find_visible_trees(&self) -> usize {
+        self.0.positions().filter(|&(x, y)| DIRS.iter().any(|&dir| self.look(x, y, dir).0)).count()
+    }
+    /// The best scenic score, or 0 for an empty forest.
+    fn find_scenic_score(&self) -> usize {
+        self.0.positions().map(|(x, y)| DIRS.iter().map(|&dir| self.look(x, y, dir).1).product()).max().unwrap_or(0)
+    }
+}
+
+const DIRS: [(isize, isize); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Parsed<'a> = Forest;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Forest> {
+        let grid = Grid::parse(input, |_, _, c| c.to_digit(10).map(|h| h as u8)).map_err(|err| match err {
+            GridError::Cell(c) => ParseError::Height(Location::of(input, c)),
+            GridError::Width(line) => ParseError::Width(Location::of(input, line)),
+        })?;
+        Ok(Forest(grid))
+    }
+
+    fn part1(forest: &Forest, _: &()) -> usize {
+        forest.find_visible_trees()
+    }
+
+    fn part2(forest: &Forest, _: &()) -> usize {
+        forest.find_scenic_score()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day8/sample8.txt");
+
+    #[test_case(SAMPLE => 21 ; "sample")]
+    #[test_case("" => 0 ; "empty")]
+    fn test_part1(input: &str) -> usize {
+        Day8::part1(&Day8::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 8 ; "sample")]
+    #[test_case("" => 0 ; "empty")]
+    fn test_part2(input: &str) -> usize {
+        Day8::part2(&Day8::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day8::parse("303\n2a5\n").unwrap_err();
+        assert_eq!("tree heights must be digits at line 2, column 2: `a`", err.to_string());
+    }
+}
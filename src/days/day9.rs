@@ -0,0 +1,135 @@
+use color_eyre::eyre::Result;
+use crate::{geom::Point, parse::Location, runner::Solution};
+use std::collections::HashSet;
+use std::fmt;
+
+pub const INPUT: &str = include_str!("../../bin/day9/input9.txt");
+
+#[derive(Debug)]
+pub enum ParseError {
+    Move(Location),
+    Direction(Location),
+    Distance(Location),
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ParseError::Move(loc) => write!(f, "expected a direction and a distance at {loc}"),
+            ParseError::Direction(loc) => write!(f, "expected R, L, U or D at {loc}"),
+            ParseError::Distance(loc) => write!(f, "invalid distance at {loc}"),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[derive(Debug, Clone)]
+struct Rope {
+    nodes: Vec<Point>,
+    visited: HashSet<Point>,
+}
+
+impl Rope {
+    fn new(knots: usize) -> Self {
+        Self {
+            nodes: vec![Point::default(); knots],
+            visited: HashSet::new(),
+        }
+    }
+    fn move_head(&mut self, dir: char, dist: usize) {
+        let step = match dir {
+            'R' => Point::new(1, 0),
+            'L' => Point::new(-1, 0),
+            'U' => Point::new(0, 1),
+            'D' => Point::new(0, -1),
+            _ => unreachable!("directions are checked while parsing"),
+        };
+        for _ in 0..dist {
+            self.nodes[0] += step;
+            for idx in 0..self.nodes.len() - 1 {
+                self.move_tail(idx);
+            }
+        }
+    }
+    fn move_tail(&mut self, head_idx: usize) {
+        let head = self.nodes[head_idx];
+        let is_last = head_idx + 2 == self.nodes.len();
+        let tail = &mut self.nodes[head_idx + 1];
+
+        if head.chebyshev(*tail) > 1 {
+            *tail += (head - *tail).signum();
+        }
+
+        if is_last {
+            self.visited.insert(*tail);
+        }
+    }
+
+    fn visited_once(&self) -> usize {
+        self.visited.len()
+    }
+}
+
+fn solve(moves: &[(char, usize)], knots: usize) -> usize {
+    let mut rope = Rope::new(knots);
+    moves.iter().for_each(|&(dir, dist)| rope.move_head(dir, dist));
+    rope.visited_once()
+}
+
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Parsed<'a> = Vec<(char, usize)>;
+    type Params = ();
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<(char, usize)>> {
+        let moves = input.lines().map(|line| {
+            let (dir, dist) = line.split_once(' ').ok_or_else(|| ParseError::Move(Location::of(input, line)))?;
+            let dir = match dir {
+                "R" | "L" | "U" | "D" => dir.as_bytes()[0] as char,
+                _ => return Err(ParseError::Direction(Location::of(input, dir))),
+            };
+            let dist = dist.parse().map_err(|_| ParseError::Distance(Location::of(input, dist)))?;
+            Ok((dir, dist))
+        }).collect::<Result<_, _>>()?;
+        Ok(moves)
+    }
+
+    fn part1(moves: &Vec<(char, usize)>, _: &()) -> usize {
+        solve(moves, 2)
+    }
+
+    fn part2(moves: &Vec<(char, usize)>, _: &()) -> usize {
+        solve(moves, 10)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    const SAMPLE: &str = include_str!("../../bin/day9/sample9.txt");
+    const LARGER_SAMPLE: &str = "R 5\nU 8\nL 8\nD 3\nR 17\nD 10\nL 25\nU 20\n";
+
+    #[test_case(SAMPLE => 13 ; "sample")]
+    fn test_part1(input: &str) -> usize {
+        Day9::part1(&Day9::parse(input).unwrap(), &())
+    }
+
+    #[test_case(SAMPLE => 1 ; "sample")]
+    #[test_case(LARGER_SAMPLE => 36 ; "larger sample")]
+    fn test_part2(input: &str) -> usize {
+        Day9::part2(&Day9::parse(input).unwrap(), &())
+    }
+
+
+    #[test]
+    fn test_parse_error() {
+        let err = Day9::parse("R 4\nX 4\n").unwrap_err();
+        assert_eq!("expected R, L, U or D at line 2, column 1: `X`", err.to_string());
+    }
+}
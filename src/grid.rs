@@ -0,0 +1,132 @@
+use std::ops::{Index, IndexMut};
+
+/// A dense, rectangular grid stored row by row and indexed by `(x, y)`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+/// Why a character grid failed to parse, pointing at the offending input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GridError<'a> {
+    /// The cell callback rejected this character.
+    Cell(&'a str),
+    /// This row's length differs from the first row's.
+    Width(&'a str),
+}
+
+impl<T: Clone> Grid<T> {
+    pub fn new(width: usize, height: usize, fill: T) -> Self {
+        Self { width, height, cells: vec![fill; width * height] }
+    }
+}
+
+impl<T> Grid<T> {
+    /// Builds a grid from its rows, or `None` if they are not all the same length.
+    pub fn from_rows(rows: Vec<Vec<T>>) -> Option<Self> {
+        let width = rows.first().map_or(0, Vec::len);
+        if rows.iter().any(|row| row.len() != width) {
+            return None;
+        }
+        let height = rows.len();
+        Some(Self { width, height, cells: rows.into_iter().flatten().collect() })
+    }
+
+    /// Parses one character per cell, one row per line. `cell` receives the
+    /// position and character and returns `None` to reject it.
+    pub fn parse(input: &str, mut cell: impl FnMut(usize, usize, char) -> Option<T>) -> Result<Self, GridError<'_>> {
+        let mut rows = Vec::new();
+        for (y, line) in input.lines().enumerate() {
+            let row = line.char_indices().enumerate().map(|(x, (idx, c))| {
+                cell(x, y, c).ok_or(GridError::Cell(&line[idx..idx + c.len_utf8()]))
+            }).collect::<Result<Vec<_>, _>>()?;
+            if rows.first().is_some_and(|first: &Vec<T>| first.len() != row.len()) {
+                return Err(GridError::Width(line));
+            }
+            rows.push(row);
+        }
+        Ok(Self::from_rows(rows).expect("rows were checked to have the same width"))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        x < self.width && y < self.height
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        self.contains(x, y).then(|| &self.cells[y * self.width + x])
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        self.contains(x, y).then(|| &mut self.cells[y * self.width + x])
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> {
+        self.cells.chunks(self.width.max(1))
+    }
+
+    /// Every `(x, y)` position, row by row.
+    pub fn positions(&self) -> impl Iterator<Item = (usize, usize)> {
+        let width = self.width;
+        (0..self.height).flat_map(move |y| (0..width).map(move |x| (x, y)))
+    }
+
+    /// The up to four orthogonal neighbours of `(x, y)` that lie inside the grid.
+    pub fn neighbours(&self, x: usize, y: usize) -> impl Iterator<Item = (usize, usize)> {
+        let (width, height) = (self.width, self.height);
+        [(0, -1), (-1, 0), (1, 0), (0, 1)].into_iter().filter_map(move |(dx, dy)| {
+            let (nx, ny) = (x.checked_add_signed(dx)?, y.checked_add_signed(dy)?);
+            (nx < width && ny < height).then_some((nx, ny))
+        })
+    }
+}
+
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &T {
+        self.get(x, y).unwrap_or_else(|| panic!("({x}, {y}) is outside the {}x{} grid", self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(x, y).unwrap_or_else(|| panic!("({x}, {y}) is outside the {width}x{height} grid"))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse() {
+        let grid = Grid::parse("12\n34\n", |_, _, c| c.to_digit(10)).unwrap();
+        assert_eq!((2, 2), (grid.width(), grid.height()));
+        assert_eq!(3, grid[(0, 1)]);
+        assert_eq!(None, grid.get(2, 0));
+        assert_eq!(vec![&[1, 2][..], &[3, 4][..]], grid.rows().collect::<Vec<_>>());
+
+        let input = "12\n3x\n";
+        assert_eq!(Err(GridError::Cell(&input[4..5])), Grid::parse(input, |_, _, c| c.to_digit(10)));
+        let input = "12\n345\n";
+        assert_eq!(Err(GridError::Width(&input[3..6])), Grid::parse(input, |_, _, c| c.to_digit(10)));
+    }
+    #[test]
+    fn test_neighbours() {
+        let grid = Grid::new(3, 2, 0);
+        assert_eq!(vec![(1, 0), (0, 1)], grid.neighbours(0, 0).collect::<Vec<_>>());
+        assert_eq!(vec![(1, 0), (0, 1), (2, 1)], grid.neighbours(1, 1).collect::<Vec<_>>());
+        assert_eq!(6, grid.positions().count());
+    }
+}
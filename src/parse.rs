@@ -0,0 +1,127 @@
+use nom::{
+    character::complete::{char, digit1},
+    combinator::{map_res, opt, recognize},
+    sequence::preceded,
+    IResult,
+};
+use std::{
+    borrow::Cow,
+    fmt::{self, Display},
+    str::FromStr,
+};
+
+/// Converts CRLF line endings to LF, borrowing when there is nothing to convert.
+pub fn normalize(input: &str) -> Cow<'_, str> {
+    if input.contains('\r') {
+        Cow::Owned(input.replace("\r\n", "\n"))
+    } else {
+        Cow::Borrowed(input)
+    }
+}
+
+/// Splits `input` into blocks separated by blank lines, whatever the line endings.
+/// Blocks never include their trailing line break.
+pub fn blocks(input: &str) -> Vec<&str> {
+    let mut blocks = Vec::new();
+    let (mut start, mut end, mut pos) = (None, 0, 0);
+    for line in input.split_inclusive('\n') {
+        let content = line.trim_end_matches(['\r', '\n']);
+        if content.trim().is_empty() {
+            if let Some(st) = start.take() {
+                blocks.push(&input[st..end]);
+            }
+        } else {
+            start.get_or_insert(pos);
+            end = pos + content.len();
+        }
+        pos += line.len();
+    }
+    if let Some(st) = start {
+        blocks.push(&input[st..end]);
+    }
+    blocks
+}
+
+/// Position of a parse error: 1-based line and column plus the offending text.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Location {
+    pub line: usize,
+    pub column: usize,
+    pub text: String,
+}
+
+impl Location {
+    /// Locates `fragment` inside `input`. `fragment` should be a subslice of `input`
+    /// (as handed out by `lines`, `split` or a nom parser); otherwise it is reported
+    /// without a position.
+    pub fn of(input: &str, fragment: &str) -> Self {
+        let text = fragment.lines().next().unwrap_or_default().to_string();
+        let (start, ptr) = (input.as_ptr() as usize, fragment.as_ptr() as usize);
+        if ptr < start || ptr + fragment.len() > start + input.len() {
+            return Self { line: 0, column: 0, text };
+        }
+        let before = &input[..ptr - start];
+        let line_start = before.rfind('\n').map_or(0, |idx| idx + 1);
+        Self {
+            line: before.matches('\n').count() + 1,
+            column: before[line_start..].chars().count() + 1,
+            text,
+        }
+    }
+}
+
+impl Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}, column {}: `{}`", self.line, self.column, self.text)
+    }
+}
+
+/// The input a failed nom parser stopped at, or `fallback` if it ran out of input.
+pub fn unparsed<'a>(err: &nom::Err<nom::error::Error<&'a str>>, fallback: &'a str) -> &'a str {
+    match err {
+        nom::Err::Error(e) | nom::Err::Failure(e) => e.input,
+        nom::Err::Incomplete(_) => fallback,
+    }
+}
+
+/// Parses an unsigned decimal number.
+pub fn unsigned<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(digit1, str::parse)(input)
+}
+
+/// Parses a decimal number with an optional leading `-`.
+pub fn signed<T: FromStr>(input: &str) -> IResult<&str, T> {
+    map_res(recognize(preceded(opt(char('-')), digit1)), str::parse)(input)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use test_case::test_case;
+
+    #[test_case("a\n\nb\nc\n" ; "lf")]
+    #[test_case("a\r\n\r\nb\r\nc\r\n" ; "crlf")]
+    #[test_case("\na\n\n\n\nb\r\nc" ; "mixed")]
+    fn test_blocks(input: &str) {
+        let blocks: Vec<Vec<&str>> = blocks(input).into_iter().map(|b| b.lines().collect()).collect();
+        assert_eq!(vec![vec!["a"], vec!["b", "c"]], blocks);
+    }
+    #[test]
+    fn test_location() {
+        let input = "abc\ndef ghi\r\njkl";
+        assert_eq!(Location { line: 2, column: 5, text: "ghi".into() }, Location::of(input, &input[8..11]));
+        assert_eq!(Location { line: 3, column: 1, text: "jkl".into() }, Location::of(input, &input[13..]));
+        assert_eq!(0, Location::of(input, "ghi").line);
+    }
+    #[test]
+    fn test_normalize() {
+        assert_eq!("a\nb\n", normalize("a\r\nb\r\n"));
+        assert!(matches!(normalize("a\nb"), Cow::Borrowed(_)));
+    }
+    #[test]
+    fn test_numbers() {
+        assert_eq!(Ok((", 3", 42_u32)), unsigned::<u32>("42, 3"));
+        assert_eq!(Ok(("", -17_i64)), signed::<i64>("-17"));
+        assert!(unsigned::<u32>("-17").is_err());
+    }
+}
@@ -0,0 +1,245 @@
+use crate::parse::normalize;
+use camino::Utf8Path;
+use color_eyre::eyre::{bail, eyre, Result, WrapErr};
+use std::{
+    borrow::Cow,
+    fmt::Display,
+    hint::black_box,
+    io::Read,
+    time::{Duration, Instant},
+};
+
+/// A day's puzzle split into a fallible parse stage and the two answer stages.
+pub trait Solution {
+    /// The puzzle input after parsing, possibly borrowing from the raw text.
+    type Parsed<'a>;
+    /// Knobs the parts depend on, e.g. the row scanned by day 15.
+    type Params: Default;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Parsed<'_>>;
+    fn part1(parsed: &Self::Parsed<'_>, params: &Self::Params) -> Self::Answer1;
+    fn part2(parsed: &Self::Parsed<'_>, params: &Self::Params) -> Self::Answer2;
+
+    /// Rejects params that don't fit the parsed input, e.g. a start that isn't in
+    /// it. Runs before either part.
+    fn check(_parsed: &Self::Parsed<'_>, _params: &Self::Params) -> Result<()> {
+        Ok(())
+    }
+
+    /// Params selecting an alternative solver by name, e.g. `flood` for day 14.
+    fn variant(_name: &str) -> Option<Self::Params> {
+        None
+    }
+}
+
+/// The default params, or those of the named `variant`.
+pub fn params<S: Solution>(variant: Option<&str>) -> Result<S::Params> {
+    match variant {
+        None => Ok(S::Params::default()),
+        Some(name) => S::variant(name).ok_or_else(|| eyre!("no such variant: {name}")),
+    }
+}
+
+/// Parses `input` and returns the answer to `part` (1 or 2), using the params of
+/// `variant` if given.
+pub fn answer<S: Solution>(input: &str, part: u8, variant: Option<&str>) -> Result<String> {
+    let input = normalize(input);
+    let params = params::<S>(variant)?;
+    let parsed = S::parse(&input)?;
+    S::check(&parsed, &params)?;
+    match part {
+        1 => Ok(S::part1(&parsed, &params).to_string()),
+        2 => Ok(S::part2(&parsed, &params).to_string()),
+        _ => bail!("no such part: {part}"),
+    }
+}
+
+/// Parses `input` and solves both parts with `params`, formatting the answers.
+pub fn solve<S: Solution>(input: &str, params: &S::Params) -> Result<(String, String)> {
+    let input = normalize(input);
+    let parsed = S::parse(&input)?;
+    S::check(&parsed, params)?;
+    Ok((S::part1(&parsed, params).to_string(), S::part2(&parsed, params).to_string()))
+}
+
+/// Times one run of the parse, part 1 and part 2 stages of `S` on `input`.
+pub fn time_stages<S: Solution>(input: &str, variant: Option<&str>) -> Result<[Duration; 3]> {
+    let input = normalize(input);
+    let params = params::<S>(variant)?;
+    let start = Instant::now();
+    let parsed = S::parse(&input)?;
+    let parse = start.elapsed();
+    S::check(&parsed, &params)?;
+    let start = Instant::now();
+    black_box(S::part1(&parsed, &params));
+    let part1 = start.elapsed();
+    let start = Instant::now();
+    black_box(S::part2(&parsed, &params));
+    let part2 = start.elapsed();
+    Ok([parse, part1, part2])
+}
+
+/// Entry point of the `dayN` binaries: loads the input and prints both parts.
+pub fn run<S: Solution>(bundled: &'static str) -> Result<()> {
+    color_eyre::install()?;
+    run_with::<S>(bundled, &params::<S>(flag("--variant").as_deref())?)
+}
+
+/// Like `run`, but with `params` in place of the ones `--variant` picks.
+pub fn run_with<S: Solution>(bundled: &'static str, params: &S::Params) -> Result<()> {
+    let input = load_input(input_flag().as_deref(), bundled)?;
+    let input = normalize(&input);
+    let parsed = S::parse(&input)?;
+    S::check(&parsed, params)?;
+    println!("{}", S::part1(&parsed, params));
+    println!("{}", S::part2(&parsed, params));
+    Ok(())
+}
+
+/// Which parts to run, as given to `--part`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Part {
+    One,
+    Two,
+    Both,
+}
+
+impl Part {
+    pub fn parse(s: &str) -> Result<Self> {
+        match s {
+            "1" => Ok(Part::One),
+            "2" => Ok(Part::Two),
+            "both" => Ok(Part::Both),
+            _ => bail!("invalid part `{s}`, expected 1, 2 or both"),
+        }
+    }
+    pub fn includes(&self, n: u8) -> bool {
+        matches!((self, n), (Part::Both, _) | (Part::One, 1) | (Part::Two, 2))
+    }
+}
+
+/// Stable fingerprint of an input, independent of its line endings (FNV-1a, hex encoded).
+pub fn input_hash(input: &str) -> String {
+    let hash = normalize(input).bytes().fold(0xcbf2_9ce4_8422_2325_u64, |h, b| (h ^ b as u64).wrapping_mul(0x0100_0000_01b3));
+    format!("{hash:016x}")
+}
+
+/// Returns the value passed to `--input` on the command line, if any.
+pub fn input_flag() -> Option<String> {
+    flag("--input")
+}
+
+/// Returns the value following `name` on the command line, if any.
+pub fn flag(name: &str) -> Option<String> {
+    std::env::args().skip_while(|arg| arg != name).nth(1)
+}
+
+/// Whether `name` was given on the command line, for flags without a value.
+pub fn switch(name: &str) -> bool {
+    std::env::args().any(|arg| arg == name)
+}
+
+/// Loads puzzle input from `path`, from stdin when `path` is `-`,
+/// or falls back to the `bundled` input when no path is given.
+pub fn load_input(path: Option<&str>, bundled: &'static str) -> Result<Cow<'static, str>> {
+    match path {
+        None => Ok(Cow::Borrowed(bundled)),
+        Some("-") => {
+            let mut buf = String::new();
+            std::io::stdin().read_to_string(&mut buf).wrap_err("failed to read input from stdin")?;
+            Ok(Cow::Owned(buf))
+        }
+        Some(path) => {
+            let path = Utf8Path::new(path);
+            std::fs::read_to_string(path)
+                .map(Cow::Owned)
+                .wrap_err_with(|| format!("failed to read input from {path}"))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days::*;
+
+    #[test]
+    fn test_input_hash() {
+        assert_eq!("cbf29ce484222325", input_hash(""));
+        assert_eq!("af63dc4c8601ec8c", input_hash("a"));
+        assert_eq!(input_hash("a\nb\n"), input_hash("a\r\nb\r\n"));
+    }
+    struct CarriageReturns;
+
+    impl Solution for CarriageReturns {
+        type Parsed<'a> = &'a str;
+        type Params = ();
+        type Answer1 = usize;
+        type Answer2 = usize;
+
+        fn parse(input: &str) -> Result<&str> {
+            Ok(input)
+        }
+        fn part1(input: &&str, _: &()) -> usize {
+            input.matches('\r').count()
+        }
+        fn part2(input: &&str, _: &()) -> usize {
+            input.len()
+        }
+    }
+
+    #[test]
+    fn test_solve_normalizes() {
+        assert_eq!(("0".to_string(), "4".to_string()), solve::<CarriageReturns>("a\r\nb\r\n", &()).unwrap());
+        assert_eq!("0", answer::<CarriageReturns>("a\r\nb\r\n", 1, None).unwrap());
+    }
+    #[test]
+    fn test_part() {
+        assert_eq!(Part::Two, Part::parse("2").unwrap());
+        assert!(Part::parse("both").unwrap().includes(1));
+        assert!(!Part::One.includes(2));
+        assert_eq!("invalid part `3`, expected 1, 2 or both", Part::parse("3").unwrap_err().to_string());
+    }
+
+    /// Both answers for `sample` with CRLF line endings, handed to `S::parse`
+    /// as is rather than through `solve`, which would normalize them.
+    fn crlf_answers<S: Solution>(sample: &str, params: &S::Params) -> [String; 2] {
+        let crlf = sample.replace("\r\n", "\n").replace('\n', "\r\n");
+        let parsed = S::parse(&crlf).unwrap();
+        [S::part1(&parsed, params).to_string(), S::part2(&parsed, params).to_string()]
+    }
+
+    const DAY10_IMAGE: &str = concat!(
+        "##..##..##..##..##..##..##..##..##..##..\r\n",
+        "###...###...###...###...###...###...###.\r\n",
+        "####....####....####....####....####....\r\n",
+        "#####.....#####.....#####.....#####.....\r\n",
+        "######......######......######......####\r\n",
+        "#######.......#######.......#######.....\r\n",
+    );
+
+    #[test]
+    fn test_crlf_samples() {
+        assert_eq!(["24000", "45000"], crlf_answers::<day1::Day1>(include_str!("../bin/day1/sample1.txt"), &Default::default()));
+        assert_eq!(["15", "12"], crlf_answers::<day2::Day2>(include_str!("../bin/day2/sample2.txt"), &Default::default()));
+        assert_eq!(["157", "70"], crlf_answers::<day3::Day3>(include_str!("../bin/day3/sample3.txt"), &Default::default()));
+        assert_eq!(["2", "4"], crlf_answers::<day4::Day4>(include_str!("../bin/day4/sample4.txt"), &Default::default()));
+        assert_eq!(["CMZ", "MCD"], crlf_answers::<day5::Day5>(include_str!("../bin/day5/sample5.txt"), &Default::default()));
+        assert_eq!(["7", "19"], crlf_answers::<day6::Day6>(include_str!("../bin/day6/sample6.txt"), &Default::default()));
+        assert_eq!(["95437", "24933642"], crlf_answers::<day7::Day7>(include_str!("../bin/day7/sample7.txt"), &Default::default()));
+        assert_eq!(["21", "8"], crlf_answers::<day8::Day8>(include_str!("../bin/day8/sample8.txt"), &Default::default()));
+        assert_eq!(["13", "1"], crlf_answers::<day9::Day9>(include_str!("../bin/day9/sample9.txt"), &Default::default()));
+        assert_eq!(["13140", DAY10_IMAGE], crlf_answers::<day10::Day10>(include_str!("../bin/day10/sample10.txt"), &Default::default()));
+        assert_eq!(["10605", "2713310158"], crlf_answers::<day11::Day11>(include_str!("../bin/day11/sample11.txt"), &Default::default()));
+        assert_eq!(["31", "29"], crlf_answers::<day12::Day12>(include_str!("../bin/day12/sample12.txt"), &Default::default()));
+        assert_eq!(["13", "140"], crlf_answers::<day13::Day13>(include_str!("../bin/day13/sample13.txt"), &Default::default()));
+        assert_eq!(["24", "93"], crlf_answers::<day14::Day14>(include_str!("../bin/day14/sample14.txt"), &Default::default()));
+        assert_eq!(["26", "56000011"], crlf_answers::<day15::Day15>(include_str!("../bin/day15/sample15.txt"), &day15::Params { row: 10, limit: 20 }));
+        assert_eq!(["1651", "1707"], crlf_answers::<day16::Day16>(include_str!("../bin/day16/sample16.txt"), &Default::default()));
+        assert_eq!(["3068", "1514285714288"], crlf_answers::<day17::Day17>(include_str!("../bin/day17/sample17.txt"), &Default::default()));
+        assert_eq!(["64", "58"], crlf_answers::<day18::Day18>(include_str!("../bin/day18/sample18.txt"), &Default::default()));
+        assert_eq!(["33", "3472"], crlf_answers::<day19::Day19>(include_str!("../bin/day19/sample19.txt"), &Default::default()));
+    }
+}
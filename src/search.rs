@@ -0,0 +1,124 @@
+use std::collections::{HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+
+/// Breadth-first search from all of `starts` at once. Returns the number of steps
+/// to the nearest node satisfying `goal`, or `None` if no such node is reachable.
+pub fn bfs<N, I>(
+    starts: impl IntoIterator<Item = N>,
+    mut neighbours: impl FnMut(&N) -> I,
+    mut goal: impl FnMut(&N) -> bool,
+) -> Option<usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut seen = HashSet::new();
+    let mut queue: VecDeque<(N, usize)> = starts.into_iter().filter(|n| seen.insert(n.clone())).map(|n| (n, 0)).collect();
+    while let Some((node, steps)) = queue.pop_front() {
+        if goal(&node) { return Some(steps) }
+        for next in neighbours(&node) {
+            if seen.insert(next.clone()) {
+                queue.push_back((next, steps + 1));
+            }
+        }
+    }
+    None
+}
+
+/// Breadth-first distances from `start` to every node reachable from it.
+pub fn bfs_distances<N, I>(start: N, mut neighbours: impl FnMut(&N) -> I) -> HashMap<N, usize>
+where
+    N: Clone + Eq + Hash,
+    I: IntoIterator<Item = N>,
+{
+    let mut dist = HashMap::from([(start.clone(), 0)]);
+    let mut queue = VecDeque::from([start]);
+    while let Some(node) = queue.pop_front() {
+        let steps = dist[&node];
+        for next in neighbours(&node) {
+            dist.entry(next.clone()).or_insert_with(|| {
+                queue.push_back(next);
+                steps + 1
+            });
+        }
+    }
+    dist
+}
+
+/// All-pairs shortest path lengths between nodes `0..n`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DistanceMatrix {
+    n: usize,
+    dist: Vec<Option<u64>>,
+}
+
+impl DistanceMatrix {
+    /// Floyd–Warshall over `n` nodes joined by the directed, weighted `edges`.
+    pub fn floyd_warshall(n: usize, edges: impl IntoIterator<Item = (usize, usize, u64)>) -> Self {
+        let mut dist = vec![None; n * n];
+        for i in 0..n {
+            dist[i * n + i] = Some(0);
+        }
+        for (from, to, weight) in edges {
+            let d = &mut dist[from * n + to];
+            *d = Some(d.map_or(weight, |d: u64| d.min(weight)));
+        }
+        for k in 0..n {
+            for i in 0..n {
+                let Some(ik) = dist[i * n + k] else { continue };
+                for j in 0..n {
+                    let Some(kj) = dist[k * n + j] else { continue };
+                    let d = &mut dist[i * n + j];
+                    if d.is_none_or(|d| ik + kj < d) {
+                        *d = Some(ik + kj);
+                    }
+                }
+            }
+        }
+        Self { n, dist }
+    }
+
+    /// Length of the shortest path from `from` to `to`, or `None` if there is none.
+    pub fn get(&self, from: usize, to: usize) -> Option<u64> {
+        self.dist[from * self.n + to]
+    }
+
+    pub fn len(&self) -> usize {
+        self.n
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.n == 0
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn line(n: &i32) -> Vec<i32> {
+        vec![n - 1, n + 1].into_iter().filter(|n| (0..10).contains(n)).collect()
+    }
+
+    #[test]
+    fn test_bfs() {
+        assert_eq!(Some(4), bfs([3], line, |&n| n == 7));
+        assert_eq!(Some(1), bfs([0, 9], line, |&n| n == 8));
+        assert_eq!(None, bfs([3], line, |&n| n == 12));
+    }
+    #[test]
+    fn test_bfs_distances() {
+        let dist = bfs_distances(2, line);
+        assert_eq!(10, dist.len());
+        assert_eq!((2, 0, 7), (dist[&0], dist[&2], dist[&9]));
+    }
+    #[test]
+    fn test_floyd_warshall() {
+        let dist = DistanceMatrix::floyd_warshall(4, [(0, 1, 5), (1, 2, 1), (0, 2, 9), (2, 0, 1)]);
+        assert_eq!(Some(6), dist.get(0, 2));
+        assert_eq!(Some(2), dist.get(1, 0));
+        assert_eq!(Some(0), dist.get(3, 3));
+        assert_eq!(None, dist.get(0, 3));
+        assert_eq!(None, dist.get(3, 0));
+    }
+}